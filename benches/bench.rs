@@ -91,5 +91,123 @@ fn bench_batch_verify(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_batch_verify);
+fn bench_hex_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Hex Encoding");
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let sig = sk.sign(thread_rng(), b"Bench");
+    let sig_bytes: [u8; 64] = sig.into();
+    let sig_hex = encoding::encode_hex_64(&sig_bytes);
+
+    group.bench_function("encode_hex_64", |b| {
+        b.iter(|| encoding::encode_hex_64(&sig_bytes))
+    });
+    group.bench_function("decode_hex_64", |b| {
+        b.iter(|| encoding::decode_hex_64(sig_hex))
+    });
+    group.bench_function("Signature Display", |b| {
+        use std::fmt::Write;
+        let mut out = String::with_capacity(128);
+        b.iter(|| {
+            out.clear();
+            write!(out, "{}", sig).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn bench_randomize_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Key Randomization");
+    let vk = VerificationKey::from(&SigningKey::<SpendAuth>::new(thread_rng()));
+    for &n in [4usize, 8, 16, 64].iter() {
+        group.throughput(Throughput::Elements(n as u64));
+
+        let randomizers = std::iter::repeat_with(|| Randomizer::new(thread_rng()))
+            .take(n)
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(
+            BenchmarkId::new("randomize", n),
+            &randomizers,
+            |b, randomizers| {
+                b.iter(|| {
+                    randomizers
+                        .iter()
+                        .map(|r| vk.randomize(r))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("randomize_many", n),
+            &randomizers,
+            |b, randomizers| b.iter(|| vk.randomize_many(randomizers)),
+        );
+    }
+    group.finish();
+}
+
+fn bench_prepared_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Prepared Verification");
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let msg = b"Bench";
+    let sig = sk.sign(thread_rng(), msg);
+    let prepared = vk.precompute();
+
+    group.bench_function("verify", |b| b.iter(|| vk.verify(msg, &sig)));
+    group.bench_function("precompute", |b| b.iter(|| vk.precompute()));
+    group.bench_function("PreparedVerificationKey::verify", |b| {
+        b.iter(|| prepared.verify(msg, &sig))
+    });
+    group.finish();
+}
+
+fn bench_key_grouped_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Key-Grouped Batch Verification");
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    for &n in [8usize, 32, 64].iter() {
+        group.throughput(Throughput::Elements(n as u64));
+
+        let sigs = (0..n as u32)
+            .map(|i| {
+                let msg = i.to_le_bytes();
+                (msg, sk.sign(thread_rng(), &msg))
+            })
+            .collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("Verifier", n), &sigs, |b, sigs| {
+            b.iter(|| {
+                let mut batch = batch::Verifier::new();
+                for (msg, sig) in sigs.iter() {
+                    batch.queue((vk_bytes, *sig, msg));
+                }
+                batch.verify(thread_rng())
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("KeyGroupedVerifier", n),
+            &sigs,
+            |b, sigs| {
+                b.iter(|| {
+                    let mut batch = batch::KeyGroupedVerifier::new();
+                    for (msg, sig) in sigs.iter() {
+                        batch.queue((vk_bytes, *sig, msg));
+                    }
+                    batch.verify(thread_rng())
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_batch_verify,
+    bench_hex_encoding,
+    bench_randomize_many,
+    bench_prepared_verify,
+    bench_key_grouped_verify
+);
 criterion_main!(benches);
@@ -0,0 +1,209 @@
+#ifndef REDJUBJUB_H
+#define REDJUBJUB_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Selects `BindingSig`.
+ */
+#define REDJUBJUB_BINDING 0
+
+/**
+ * Selects `SpendAuthSig`.
+ */
+#define REDJUBJUB_SPENDAUTH 1
+
+/**
+ * The call succeeded.
+ */
+#define REDJUBJUB_OK 0
+
+/**
+ * A pointer was null, a length was invalid, or the signature type was unknown.
+ */
+#define REDJUBJUB_ERR_INVALID_ARGUMENT 1
+
+/**
+ * See [`Error::MalformedSigningKey`].
+ */
+#define REDJUBJUB_ERR_MALFORMED_SIGNING_KEY 2
+
+/**
+ * See [`Error::MalformedVerificationKey`].
+ */
+#define REDJUBJUB_ERR_MALFORMED_VERIFICATION_KEY 3
+
+/**
+ * See [`Error::MalformedRandomizer`].
+ */
+#define REDJUBJUB_ERR_MALFORMED_RANDOMIZER 4
+
+/**
+ * See [`Error::InvalidSignature`].
+ */
+#define REDJUBJUB_ERR_INVALID_SIGNATURE 5
+
+/**
+ * The library panicked; this indicates a bug.
+ */
+#define REDJUBJUB_ERR_PANIC 6
+
+/**
+ * See [`Error::WeakRandomness`].
+ */
+#define REDJUBJUB_ERR_WEAK_RANDOMNESS 7
+
+/**
+ * See [`Error::MalformedKeyTable`].
+ */
+#define REDJUBJUB_ERR_MALFORMED_KEY_TABLE 8
+
+/**
+ * See [`Error::MalformedSignature`].
+ */
+#define REDJUBJUB_ERR_MALFORMED_SIGNATURE 9
+
+/**
+ * See [`Error::SelfTestFailed`].
+ */
+#define REDJUBJUB_ERR_SELF_TEST_FAILED 10
+
+/**
+ * See [`Error::IdentityKey`].
+ */
+#define REDJUBJUB_ERR_IDENTITY_KEY 11
+
+/**
+ * Generate a new signing key, writing it to the 32-byte `sk_out`.
+ *
+ * # Safety
+ *
+ * `sk_out` must be valid for writes of 32 bytes.
+ */
+int32_t redjubjub_signing_key_generate(uint8_t sig_type, uint8_t *sk_out);
+
+/**
+ * Derive the verification key of the 32-byte signing key `sk`, writing it
+ * to the 32-byte `vk_out`.
+ *
+ * # Safety
+ *
+ * `sk` must be valid for reads and `vk_out` for writes of 32 bytes.
+ */
+int32_t redjubjub_verification_key(uint8_t sig_type, const uint8_t *sk, uint8_t *vk_out);
+
+/**
+ * Sign the `msg_len` bytes at `msg` with the 32-byte signing key `sk`,
+ * writing the signature to the 64-byte `sig_out`.
+ *
+ * # Safety
+ *
+ * `sk` must be valid for reads of 32 bytes, `msg` for reads of `msg_len`
+ * bytes (it may be null if `msg_len` is zero), and `sig_out` for writes of
+ * 64 bytes.
+ */
+int32_t redjubjub_sign(uint8_t sig_type,
+                       const uint8_t *sk,
+                       const uint8_t *msg,
+                       size_t msg_len,
+                       uint8_t *sig_out);
+
+/**
+ * Verify the 64-byte signature `sig` over the `msg_len` bytes at `msg` by
+ * the 32-byte verification key `vk`.
+ *
+ * # Safety
+ *
+ * `vk` must be valid for reads of 32 bytes, `msg` for reads of `msg_len`
+ * bytes (it may be null if `msg_len` is zero), and `sig` for reads of 64
+ * bytes.
+ */
+int32_t redjubjub_verify(uint8_t sig_type,
+                         const uint8_t *vk,
+                         const uint8_t *msg,
+                         size_t msg_len,
+                         const uint8_t *sig);
+
+/**
+ * Batch-verify `n` signatures, returning [`REDJUBJUB_OK`] only if all of
+ * them are valid.
+ *
+ * Signature `i` has type `sig_types[i]`, verification key
+ * `vks[32 * i..32 * (i + 1)]`, signature `sigs[64 * i..64 * (i + 1)]`, and
+ * message `msgs[i]` of length `msg_lens[i]`.
+ *
+ * # Safety
+ *
+ * `sig_types`, `msgs` and `msg_lens` must be valid for reads of `n`
+ * elements, `vks` for reads of `32 * n` bytes and `sigs` for reads of
+ * `64 * n` bytes, and each `msgs[i]` must be valid for reads of
+ * `msg_lens[i]` bytes.
+ */
+int32_t redjubjub_batch_verify(size_t n,
+                               const uint8_t *sig_types,
+                               const uint8_t *vks,
+                               const uint8_t *const *msgs,
+                               const size_t *msg_lens,
+                               const uint8_t *sigs);
+
+/**
+ * Batch-verify `n` signatures as [`redjubjub_batch_verify`] does, and if the
+ * batch fails, find the first signature that fails on its own.
+ *
+ * On failure the index of that signature is written to `invalid_index_out`,
+ * unlike the other outputs of this API, and the returned code is that
+ * signature's error. Finding it costs about one single verification per
+ * signature.
+ *
+ * # Safety
+ *
+ * As for [`redjubjub_batch_verify`], and `invalid_index_out` must be valid
+ * for writes of one `usize`.
+ */
+int32_t redjubjub_batch_verify_find_invalid(size_t n,
+                                            const uint8_t *sig_types,
+                                            const uint8_t *vks,
+                                            const uint8_t *const *msgs,
+                                            const size_t *msg_lens,
+                                            const uint8_t *sigs,
+                                            size_t *invalid_index_out);
+
+/**
+ * Randomize the 32-byte `SpendAuth` signing key `sk` with the 32-byte
+ * `randomizer`, writing the result to the 32-byte `sk_out`.
+ *
+ * # Safety
+ *
+ * `sk` and `randomizer` must be valid for reads and `sk_out` for writes of
+ * 32 bytes.
+ */
+int32_t redjubjub_randomize_signing_key(const uint8_t *sk,
+                                        const uint8_t *randomizer,
+                                        uint8_t *sk_out);
+
+/**
+ * Randomize the 32-byte `SpendAuth` verification key `vk` with the 32-byte
+ * `randomizer`, writing the result to the 32-byte `vk_out`.
+ *
+ * # Safety
+ *
+ * `vk` and `randomizer` must be valid for reads and `vk_out` for writes of
+ * 32 bytes.
+ */
+int32_t redjubjub_randomize_verification_key(const uint8_t *vk,
+                                             const uint8_t *randomizer,
+                                             uint8_t *vk_out);
+
+/**
+ * Run [`crate::self_test`], returning [`REDJUBJUB_OK`] if the library
+ * computes the expected answers on this platform.
+ */
+int32_t redjubjub_self_test(void);
+
+#endif  /* REDJUBJUB_H */
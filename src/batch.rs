@@ -17,6 +17,29 @@
 //! caller code (which must assemble a batch of signatures across work-items),
 //! and loss of the ability to easily pinpoint failing signatures.
 //!
+//! A single [`Verifier`] accepts both `SpendAuth` and `Binding` items, and
+//! checks all of them in one multiscalar multiplication, so a Zcash
+//! transaction's signatures can be verified together:
+//!
+//! ```
+//! use rand::thread_rng;
+//! use redjubjub::*;
+//!
+//! let mut batch = batch::Verifier::new();
+//! let msg = b"sighash";
+//!
+//! let spend_sk = SigningKey::<SpendAuth>::new(thread_rng());
+//! let spend_sig = spend_sk.sign(thread_rng(), msg);
+//! batch.queue((VerificationKey::from(&spend_sk).into(), spend_sig, msg));
+//!
+//! let binding_sk = SigningKey::<Binding>::new(thread_rng());
+//! let binding_sig = binding_sk.sign(thread_rng(), msg);
+//! batch.queue((VerificationKey::from(&binding_sk).into(), binding_sig, msg));
+//!
+//! assert!(batch.verify(thread_rng()).is_ok());
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
 
 use rand_core::{CryptoRng, RngCore};
 
@@ -27,8 +50,14 @@ use crate::*;
 /// This struct exists to allow batch processing to be decoupled from the
 /// lifetime of the message. This is useful when using the batch verification API
 /// in an async context.
+///
+/// Alongside the `reddsa` item it records the signature type's tag, so that
+/// [`Verifier::verify_or_recover`] can report per-type counts.
 #[derive(Clone, Debug)]
-pub struct Item(reddsa::batch::Item<sapling::SpendAuth, sapling::Binding>);
+pub struct Item(
+    reddsa::batch::Item<sapling::SpendAuth, sapling::Binding>,
+    u8,
+);
 
 impl<'msg, M: AsRef<[u8]>>
     From<(
@@ -44,7 +73,10 @@ impl<'msg, M: AsRef<[u8]>>
             &'msg M,
         ),
     ) -> Self {
-        Self(reddsa::batch::Item::from_spendauth(vk_bytes.0, sig.0, msg))
+        Self(
+            reddsa::batch::Item::from_spendauth(vk_bytes.0, sig.0, msg),
+            <SpendAuth as crate::private::Sealed>::TAG,
+        )
     }
 }
 
@@ -54,11 +86,95 @@ impl<'msg, M: AsRef<[u8]>> From<(VerificationKeyBytes<Binding>, Signature<Bindin
     fn from(
         (vk_bytes, sig, msg): (VerificationKeyBytes<Binding>, Signature<Binding>, &'msg M),
     ) -> Self {
-        Self(reddsa::batch::Item::from_binding(vk_bytes.0, sig.0, msg))
+        Self(
+            reddsa::batch::Item::from_binding(vk_bytes.0, sig.0, msg),
+            <Binding as crate::private::Sealed>::TAG,
+        )
+    }
+}
+
+/// A type holding a signature that can be queued for batch verification
+/// directly, without first converting it into a tuple.
+///
+/// Node implementations can implement this for their own spend or output
+/// description types, and then pass them to [`Verifier::queue_from`].
+pub trait BatchItemSource {
+    /// The type of the signature, `SpendAuth` or `Binding`.
+    type SigType: SigType;
+
+    /// The verification key the signature is checked against.
+    fn vk_bytes(&self) -> VerificationKeyBytes<Self::SigType>;
+
+    /// The signature.
+    fn signature(&self) -> Signature<Self::SigType>;
+
+    /// The signed message.
+    fn payload(&self) -> &[u8];
+}
+
+impl<'a, S: BatchItemSource> From<&'a S> for Item {
+    fn from(source: &'a S) -> Self {
+        Self(
+            <S::SigType as crate::private::Sealed>::batch_item(
+                source.vk_bytes().0,
+                source.signature().0,
+                source.payload(),
+            ),
+            <S::SigType as crate::private::Sealed>::TAG,
+        )
     }
 }
 
 impl Item {
+    /// The number of bytes a queued item occupies in a [`Verifier`], for
+    /// budgeting the memory of preallocated verifiers.
+    ///
+    /// Items own no heap memory, so this is the whole cost of an item.
+    pub const fn size_hint() -> usize {
+        core::mem::size_of::<Item>()
+    }
+
+    /// Construct an item of type `T` from the raw encodings of the key and
+    /// signature, as [`crate::verify`] takes them.
+    ///
+    /// The encodings are checked when the item is verified, with the same
+    /// consensus checks as [`crate::verify`].
+    pub fn from_raw<T: SigType, M: AsRef<[u8]>>(
+        vk_bytes: [u8; 32],
+        msg: &M,
+        sig_bytes: [u8; 64],
+    ) -> Self {
+        Self(
+            <T as crate::private::Sealed>::batch_item(
+                VerificationKeyBytes::<T>::from(vk_bytes).0,
+                Signature::<T>::from(sig_bytes).0,
+                msg.as_ref(),
+            ),
+            <T as crate::private::Sealed>::TAG,
+        )
+    }
+
+    /// Construct an item for a `SpendAuth` signature by the randomization of
+    /// the verification key `ak_bytes` with `randomizer`.
+    ///
+    /// The randomized key `rk = ak + [randomizer]B` is derived here, so the
+    /// item only verifies if the signature was made with the correspondingly
+    /// randomized signing key. This lets a holder of `ak` check that spends
+    /// were authorized by keys derived from it, without trusting a separately
+    /// supplied `rk`.
+    ///
+    /// Returns [`Error::MalformedVerificationKey`] if `ak_bytes` does not
+    /// encode a valid verification key.
+    pub fn from_randomized<M: AsRef<[u8]>>(
+        ak_bytes: VerificationKeyBytes<SpendAuth>,
+        randomizer: &Randomizer,
+        sig: Signature<SpendAuth>,
+        msg: &M,
+    ) -> Result<Self, Error> {
+        let rk = VerificationKey::try_from(ak_bytes)?.randomize(randomizer);
+        Ok(Self::from((VerificationKeyBytes::from(rk), sig, msg)))
+    }
+
     /// Perform non-batched verification of this `Item`.
     ///
     /// This is useful (in combination with `Item::clone`) for implementing fallback
@@ -72,9 +188,81 @@ impl Item {
     }
 }
 
-#[derive(Default)]
+/// Statistics about a batch checked by [`Verifier::verify_or_recover`], for
+/// exporting metrics.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BatchStats {
+    /// The number of queued items.
+    pub items: usize,
+    /// The number of queued `SpendAuth` items.
+    pub spendauth: usize,
+    /// The number of queued `Binding` items.
+    pub binding: usize,
+    /// The number of terms in the multiscalar multiplication: one per
+    /// verification key and per `R`, plus the two basepoints.
+    pub multiscalar_size: usize,
+    /// The number of valid signatures. This equals `items` if the batch
+    /// verified; otherwise each item was checked on its own to count them.
+    pub valid: usize,
+    /// The first item that failed on its own, if the batch failed.
+    pub first_invalid: Option<ItemError>,
+}
+
+/// The failure of one item of a batch, with its position in the order the
+/// items were queued.
+///
+/// Under `std` the underlying [`Error`] is available as the
+/// [`source`](std::error::Error::source) of this error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ItemError {
+    /// The index of the item in the batch.
+    pub index: usize,
+    /// Why the item failed.
+    pub error: Error,
+}
+
+impl core::fmt::Display for ItemError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Batch item {} failed: {}", self.index, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(all(feature = "core-error", not(feature = "std")))]
+impl core::error::Error for ItemError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Clone, Default)]
 /// A batch verification context.
-pub struct Verifier(reddsa::batch::Verifier<sapling::SpendAuth, sapling::Binding>);
+///
+/// Queued items are held in a single vector, which can be preallocated with
+/// [`Verifier::with_capacity`] and reused across batches with
+/// [`Verifier::verify_and_reset`] or [`Verifier::reset`].
+pub struct Verifier(Vec<Item>);
+
+impl<I: Into<Item>> Extend<I> for Verifier {
+    fn extend<T: IntoIterator<Item = I>>(&mut self, items: T) {
+        self.queue_many(items);
+    }
+}
+
+impl<I: Into<Item>> FromIterator<I> for Verifier {
+    fn from_iter<T: IntoIterator<Item = I>>(items: T) -> Self {
+        let mut verifier = Verifier::new();
+        verifier.queue_many(items);
+        verifier
+    }
+}
 
 impl Verifier {
     /// Construct a new batch verifier.
@@ -82,9 +270,55 @@ impl Verifier {
         Verifier::default()
     }
 
+    /// Construct a new batch verifier with room for `n` items before it
+    /// reallocates.
+    pub fn with_capacity(n: usize) -> Verifier {
+        Verifier(Vec::with_capacity(n))
+    }
+
+    /// The number of queued items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no items are queued.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The number of items the verifier can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Reserve room for at least `additional` more items.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    /// Discard all queued items, keeping the allocation for the next batch.
+    pub fn reset(&mut self) {
+        self.0.clear();
+    }
+
     /// Queue an Item for verification.
     pub fn queue<I: Into<Item>>(&mut self, item: I) {
-        self.0.queue(item.into().0);
+        self.0.push(item.into());
+    }
+
+    /// Queue the signature held by `source` for verification.
+    pub fn queue_from<S: BatchItemSource>(&mut self, source: &S) {
+        self.queue(source);
+    }
+
+    /// Queue every item of `items` for verification, reserving room for them
+    /// up front from the iterator's size hint.
+    pub fn queue_many<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Item>,
+    {
+        self.0.extend(items.into_iter().map(Into::into));
     }
 
     /// Perform batch verification, returning `Ok(())` if all signatures were
@@ -121,7 +355,245 @@ impl Verifier {
     ///
     /// [ps]: https://zips.z.cash/protocol/protocol.pdf#reddsabatchverify
     #[allow(non_snake_case)]
-    pub fn verify<R: RngCore + CryptoRng>(self, rng: R) -> Result<(), Error> {
-        self.0.verify(rng).map_err(|e| e.into())
+    pub fn verify<R: RngCore + CryptoRng>(mut self, rng: R) -> Result<(), Error> {
+        self.verify_and_reset(rng)
+    }
+
+    /// Perform batch verification as [`Verifier::verify`] does, then leave
+    /// the verifier empty but with its allocation intact, ready for the next
+    /// batch.
+    ///
+    /// The multiscalar multiplication still allocates its own scratch space
+    /// for each batch.
+    pub fn verify_and_reset<R: RngCore + CryptoRng>(&mut self, rng: R) -> Result<(), Error> {
+        let mut verifier = reddsa::batch::Verifier::new();
+        for item in self.0.drain(..) {
+            verifier.queue(item.0);
+        }
+        verifier.verify(rng).map_err(|e| e.into())
+    }
+
+    /// Verify each queued item on its own, returning the first that fails
+    /// together with its index.
+    ///
+    /// This finds which signature made a batch fail, for instance on a clone
+    /// of a verifier whose [`Verifier::verify`] returned an error. It costs
+    /// about one single verification per item.
+    pub fn verify_each(self) -> Result<(), ItemError> {
+        for (index, item) in self.0.into_iter().enumerate() {
+            item.verify_single()
+                .map_err(|error| ItemError { index, error })?;
+        }
+        Ok(())
+    }
+
+    /// Perform batch verification as [`Verifier::verify`] does, also returning
+    /// [`BatchStats`] about the batch.
+    ///
+    /// If the batch fails, every item is verified on its own to find how many
+    /// signatures were valid and which one failed first, which costs about one
+    /// single verification per item on top of the batch. The returned result
+    /// is that of the batch.
+    pub fn verify_or_recover<R: RngCore + CryptoRng>(
+        self,
+        rng: R,
+    ) -> (Result<(), Error>, BatchStats) {
+        let items = self.0.len();
+        let spendauth = self
+            .0
+            .iter()
+            .filter(|item| item.1 == <SpendAuth as crate::private::Sealed>::TAG)
+            .count();
+        let mut stats = BatchStats {
+            items,
+            spendauth,
+            binding: items - spendauth,
+            multiscalar_size: 2 + 2 * items,
+            valid: items,
+            first_invalid: None,
+        };
+
+        let mut verifier = reddsa::batch::Verifier::new();
+        for item in self.0.iter() {
+            verifier.queue(item.0.clone());
+        }
+        let result = verifier.verify(rng).map_err(Error::from);
+        if result.is_err() {
+            stats.valid = 0;
+            for (index, item) in self.0.into_iter().enumerate() {
+                match item.verify_single() {
+                    Ok(()) => stats.valid += 1,
+                    Err(error) => {
+                        stats
+                            .first_invalid
+                            .get_or_insert(ItemError { index, error });
+                    }
+                }
+            }
+        }
+        (result, stats)
+    }
+
+    /// Perform batch verification as [`Verifier::verify`] does, splitting the
+    /// batch into chunks that are verified in parallel on the rayon thread
+    /// pool.
+    ///
+    /// Returns `Ok(())` only if every chunk verifies, so the result is the same
+    /// as for [`Verifier::verify`]. Each chunk draws its random coefficients
+    /// from its own BLAKE2b-based RNG, seeded from `rng`.
+    #[cfg(feature = "rayon")]
+    pub fn verify_parallel<R: RngCore + CryptoRng>(self, mut rng: R) -> Result<(), Error> {
+        use rayon::prelude::*;
+
+        // Smaller chunks lose more to per-chunk overhead than they gain.
+        const MIN_CHUNK: usize = 64;
+
+        let chunk_size = (self.0.len() / rayon::current_num_threads()).max(MIN_CHUNK);
+        let seeds: Vec<[u8; 32]> = self
+            .0
+            .chunks(chunk_size)
+            .map(|_| {
+                let mut seed = [0; 32];
+                rng.fill_bytes(&mut seed);
+                seed
+            })
+            .collect();
+
+        self.0
+            .par_chunks(chunk_size)
+            .zip(seeds)
+            .try_for_each(|(chunk, seed)| {
+                let mut verifier = reddsa::batch::Verifier::new();
+                for item in chunk {
+                    verifier.queue(item.0.clone());
+                }
+                let rng = crate::rng::HashRng::new(b"RedJubjubParBtch").update(&seed);
+                verifier.verify(rng).map_err(Error::from)
+            })
+    }
+}
+
+/// A batch verification context specialized for many signatures of type `T`
+/// by few distinct verification keys.
+///
+/// [`Verifier`] spends two multiscalar terms on every signature, one for its
+/// `R` and one for its key. This verifier groups the queued signatures by key
+/// and sums the coefficients of each key, so `n` signatures by `k` distinct
+/// keys take `n + k + 1` terms: about half as many when one key signs
+/// everything. Messages only enter the equation through the challenges, so
+/// signatures that share a message but not a key do not collapse.
+///
+/// It accepts exactly the batches that [`Verifier`] accepts.
+#[derive(Clone, Debug)]
+pub struct KeyGroupedVerifier<T: SigType> {
+    /// The queued signatures and challenges, grouped by key.
+    keys: BTreeMap<VerificationKeyBytes<T>, Vec<(Signature<T>, jubjub::Scalar)>>,
+    len: usize,
+}
+
+impl<T: SigType> Default for KeyGroupedVerifier<T> {
+    fn default() -> Self {
+        Self {
+            keys: BTreeMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T: SigType> KeyGroupedVerifier<T> {
+    /// Construct a new batch verifier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of queued signatures.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no signatures are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of distinct verification keys among the queued signatures.
+    pub fn distinct_keys(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// The number of terms the multiscalar multiplication will have.
+    pub fn multiscalar_size(&self) -> usize {
+        self.len + self.keys.len() + 1
+    }
+
+    /// Queue a signature for verification.
+    ///
+    /// The challenge is computed here, so the message is not retained.
+    pub fn queue<M: AsRef<[u8]>>(
+        &mut self,
+        (vk_bytes, sig, msg): (VerificationKeyBytes<T>, Signature<T>, &M),
+    ) {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(crate::params::H_STAR_PERSONALIZATION)
+            .to_state()
+            .update(&sig.r_bytes())
+            .update(&vk_bytes.to_bytes())
+            .update(msg.as_ref())
+            .finalize();
+        let c = jubjub::Scalar::from_bytes_wide(hash.as_array());
+        self.keys.entry(vk_bytes).or_default().push((sig, c));
+        self.len += 1;
+    }
+
+    /// Perform batch verification, returning `Ok(())` if all signatures were
+    /// valid and `Err` otherwise.
+    ///
+    /// This checks the same equation as [`Verifier::verify`], with the terms
+    /// of each key `VK_j` collected into `[sum(z_i * c_i): vk_i = VK_j]VK_j`.
+    #[allow(non_snake_case)]
+    pub fn verify<R: RngCore + CryptoRng>(self, mut rng: R) -> Result<(), Error> {
+        let size = self.multiscalar_size();
+        let mut scalars = Vec::with_capacity(size);
+        let mut points = Vec::with_capacity(size);
+        let mut P_coeff = jubjub::Scalar::zero();
+
+        for (vk_bytes, signatures) in self.keys {
+            let VK = vk_bytes.to_point()?;
+            let mut VK_coeff = jubjub::Scalar::zero();
+            for (sig, c) in signatures {
+                let s = Option::<jubjub::Scalar>::from(jubjub::Scalar::from_bytes(&sig.s_bytes()))
+                    .ok_or(Error::InvalidSignature)?;
+                let R = Option::<jubjub::AffinePoint>::from(jubjub::AffinePoint::from_bytes(
+                    sig.r_bytes(),
+                ))
+                .ok_or(Error::InvalidSignature)?;
+
+                // A random 128-bit coefficient.
+                let mut z_bytes = [0; 32];
+                rng.fill_bytes(&mut z_bytes[..16]);
+                let z = jubjub::Scalar::from_bytes(&z_bytes).unwrap();
+
+                P_coeff -= z * s;
+                VK_coeff += z * c;
+                scalars.push(z);
+                points.push(R.into());
+            }
+            scalars.push(VK_coeff);
+            points.push(VK.into());
+        }
+
+        let P = VerificationKeyBytes::<T>::from(<T as crate::private::Sealed>::BASEPOINT_BYTES)
+            .to_point()
+            .expect("basepoint encodings are valid");
+        scalars.push(P_coeff);
+        points.push(P.into());
+
+        let check = crate::scalar_mul::vartime_multiscalar_mul(&scalars, &points);
+        if bool::from(check.is_small_order()) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
     }
 }
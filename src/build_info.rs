@@ -0,0 +1,64 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+use crate::self_test::KNOWN_ANSWERS;
+
+/// The version of the [`crate::keytable`] format.
+pub(crate) const KEY_TABLE_VERSION: u8 = 1;
+
+/// Identifiers of the algorithms this build implements; see [`build_info`].
+const ALGORITHMS: &[&str] = &[
+    "RedJubjub-SpendAuthSig",
+    "RedJubjub-BindingSig",
+    "RedJubjub-BatchVerify-Cofactored",
+    "KeyDerivation-BLAKE2b-512-RedJubjubKeyDerv",
+    "DeterministicNonce-BLAKE2b-512-RedJubjubDetSign",
+];
+
+/// A description of this build of the library, for deployments to log and
+/// compare against audit reports.
+///
+/// Two builds with equal `BuildInfo` implement the same algorithms and
+/// encodings, and check the same known answers in [`crate::self_test`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuildInfo {
+    /// The crate version.
+    pub version: &'static str,
+    /// Identifiers of the algorithms implemented, and their domain
+    /// separation strings where the crate defines its own.
+    pub algorithms: &'static [&'static str],
+    /// The version of the serialized key table format.
+    pub key_table_version: u8,
+    /// The BLAKE2b-256 digest, with personalization `RedJubjubKATDgst`, of the
+    /// known answers checked by [`crate::self_test`]. Each answer contributes
+    /// its name and hex value, each preceded by its length as a
+    /// little-endian `u64`.
+    pub known_answer_digest: [u8; 32],
+}
+
+/// Describe this build of the library; see [`BuildInfo`].
+pub fn build_info() -> BuildInfo {
+    let mut state = blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"RedJubjubKATDgst")
+        .to_state();
+    for (name, value) in KNOWN_ANSWERS.iter() {
+        for field in [name, value] {
+            state.update(&(field.len() as u64).to_le_bytes());
+            state.update(field.as_bytes());
+        }
+    }
+    let mut known_answer_digest = [0; 32];
+    known_answer_digest.copy_from_slice(state.finalize().as_bytes());
+
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        algorithms: ALGORITHMS,
+        key_table_version: KEY_TABLE_VERSION,
+        known_answer_digest,
+    }
+}
@@ -0,0 +1,118 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Allocation-free hex encoding of the 32- and 64-byte values used by this
+//! crate.
+//!
+//! These are the helpers behind the `Display` impls of [`Signature`] and the
+//! verification key types. They work on fixed-size stack buffers, so they are
+//! suitable for services that log very large numbers of keys and signatures.
+//!
+//! [`Signature`]: crate::Signature
+
+use core::{fmt, str};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Encode `N` bytes as `M = 2 * N` lowercase hex digits.
+const fn encode<const N: usize, const M: usize>(bytes: &[u8; N]) -> [u8; M] {
+    assert!(M == 2 * N);
+    let mut hex = [0; M];
+    let mut i = 0;
+    while i < N {
+        hex[2 * i] = HEX_DIGITS[(bytes[i] >> 4) as usize];
+        hex[2 * i + 1] = HEX_DIGITS[(bytes[i] & 0x0f) as usize];
+        i += 1;
+    }
+    hex
+}
+
+/// Encode 32 bytes as 64 lowercase ASCII hex digits.
+pub const fn encode_hex_32(bytes: &[u8; 32]) -> [u8; 64] {
+    encode(bytes)
+}
+
+/// Encode 64 bytes as 128 lowercase ASCII hex digits.
+pub const fn encode_hex_64(bytes: &[u8; 64]) -> [u8; 128] {
+    encode(bytes)
+}
+
+fn decode_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode exactly `2 * N` hex digits (of either case).
+fn decode<const N: usize>(hex: &[u8]) -> Option<[u8; N]> {
+    if hex.len() != 2 * N {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (decode_nibble(pair[0])? << 4) | decode_nibble(pair[1])?;
+    }
+    Some(bytes)
+}
+
+/// Decode 64 hex digits (of either case) into 32 bytes.
+///
+/// Returns `None` if `hex` has the wrong length or contains a non-hex digit.
+pub fn decode_hex_32(hex: impl AsRef<[u8]>) -> Option<[u8; 32]> {
+    decode(hex.as_ref())
+}
+
+/// Decode 128 hex digits (of either case) into 64 bytes.
+///
+/// Returns `None` if `hex` has the wrong length or contains a non-hex digit.
+pub fn decode_hex_64(hex: impl AsRef<[u8]>) -> Option<[u8; 64]> {
+    decode(hex.as_ref())
+}
+
+/// Formats a byte slice as lowercase hex, 32 bytes at a time through a stack
+/// buffer.
+pub(crate) struct Hex<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.0.chunks(32) {
+            let mut bytes = [0; 32];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            let hex = encode_hex_32(&bytes);
+            let hex = str::from_utf8(&hex[..2 * chunk.len()]).map_err(|_| fmt::Error)?;
+            f.write_str(hex)?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize a hex string of exactly `N` bytes; used by the human-readable
+/// serde representations.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_hex<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct HexVisitor<const N: usize>;
+
+    impl<'de, const N: usize> serde::de::Visitor<'de> for HexVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a hex string encoding {} bytes", N)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Self::Value, E> {
+            decode(s.as_bytes())
+                .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(s), &self))
+        }
+    }
+
+    deserializer.deserialize_str(HexVisitor::<N>)
+}
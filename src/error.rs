@@ -8,24 +8,61 @@
 // - Deirdre Connolly <deirdre@zfnd.org>
 // - Henry de Valence <hdevalence@hdevalence.ca>
 
-#[cfg(feature = "std")]
-use thiserror::Error;
+use core::fmt;
 
 /// An error related to RedJubJub signatures.
+///
+/// `Error` implements `std::error::Error` with the `std` feature. Without it,
+/// the `core-error` feature implements `core::error::Error` instead, which
+/// needs Rust 1.81.
+///
+/// New variants may be added in minor releases, so matches on `Error` outside
+/// this crate need a wildcard arm.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "std", derive(Error))]
+#[non_exhaustive]
 pub enum Error {
     /// The encoding of a signing key was malformed.
-    #[cfg_attr(feature = "std", error("Malformed signing key encoding."))]
     MalformedSigningKey,
     /// The encoding of a verification key was malformed.
-    #[cfg_attr(feature = "std", error("Malformed verification key encoding."))]
     MalformedVerificationKey,
+    /// The encoding of a randomizer was malformed.
+    MalformedRandomizer,
+    /// The encoding of a signature was malformed.
+    MalformedSignature,
     /// Signature verification failed.
-    #[cfg_attr(feature = "std", error("Invalid signature."))]
     InvalidSignature,
+    /// The output of the caller-provided RNG failed a health check.
+    WeakRandomness,
+    /// A serialized key table had an invalid header or length.
+    MalformedKeyTable,
+    /// Combining keys gave the identity, which cannot be used as a key.
+    IdentityKey,
+    /// A known-answer check in [`crate::self_test`] failed.
+    SelfTestFailed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::MalformedSigningKey => "Malformed signing key encoding.",
+            Error::MalformedVerificationKey => "Malformed verification key encoding.",
+            Error::MalformedRandomizer => "Malformed randomizer encoding.",
+            Error::MalformedSignature => "Malformed signature encoding.",
+            Error::InvalidSignature => "Invalid signature.",
+            Error::WeakRandomness => "RNG output failed a health check.",
+            Error::MalformedKeyTable => "Malformed key table.",
+            Error::IdentityKey => "Combined key is the identity.",
+            Error::SelfTestFailed => "Known-answer self-test failed.",
+        })
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(all(feature = "core-error", not(feature = "std")))]
+impl core::error::Error for Error {}
+
 impl From<reddsa::Error> for Error {
     fn from(e: reddsa::Error) -> Self {
         match e {
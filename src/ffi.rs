@@ -0,0 +1,414 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! A C ABI for single-signer signing and verification, batch verification
+//! and key randomization.
+//!
+//! All values cross the boundary as fixed-size byte buffers: signing keys,
+//! verification keys and randomizers are 32 bytes, and signatures are 64
+//! bytes, in the same encodings as the `[u8; N]` conversions of the Rust
+//! types. The signature type is selected with [`REDJUBJUB_BINDING`] or
+//! [`REDJUBJUB_SPENDAUTH`].
+//!
+//! Every function returns [`REDJUBJUB_OK`] on success or one of the
+//! `REDJUBJUB_ERR_*` codes. Output buffers are only written on success.
+//! Panics are caught at the boundary and reported as [`REDJUBJUB_ERR_PANIC`].
+//!
+//! Randomness is taken from the operating system. The C header
+//! `redjubjub.h` in the repository is generated with [cbindgen] from
+//! `cbindgen.toml`, and a static library can be built with
+//! `cargo rustc --release --features ffi --crate-type staticlib`.
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+
+use std::{panic, slice};
+
+use rand_core::OsRng;
+
+use crate::{batch, Binding, Error, Randomizer, SigType, Signature, SigningKey, SpendAuth};
+
+/// Selects `BindingSig`.
+pub const REDJUBJUB_BINDING: u8 = 0;
+/// Selects `SpendAuthSig`.
+pub const REDJUBJUB_SPENDAUTH: u8 = 1;
+
+/// The call succeeded.
+pub const REDJUBJUB_OK: i32 = 0;
+/// A pointer was null, a length was invalid, or the signature type was unknown.
+pub const REDJUBJUB_ERR_INVALID_ARGUMENT: i32 = 1;
+/// See [`Error::MalformedSigningKey`].
+pub const REDJUBJUB_ERR_MALFORMED_SIGNING_KEY: i32 = 2;
+/// See [`Error::MalformedVerificationKey`].
+pub const REDJUBJUB_ERR_MALFORMED_VERIFICATION_KEY: i32 = 3;
+/// See [`Error::MalformedRandomizer`].
+pub const REDJUBJUB_ERR_MALFORMED_RANDOMIZER: i32 = 4;
+/// See [`Error::InvalidSignature`].
+pub const REDJUBJUB_ERR_INVALID_SIGNATURE: i32 = 5;
+/// The library panicked; this indicates a bug.
+pub const REDJUBJUB_ERR_PANIC: i32 = 6;
+/// See [`Error::WeakRandomness`].
+pub const REDJUBJUB_ERR_WEAK_RANDOMNESS: i32 = 7;
+/// See [`Error::MalformedKeyTable`].
+pub const REDJUBJUB_ERR_MALFORMED_KEY_TABLE: i32 = 8;
+/// See [`Error::MalformedSignature`].
+pub const REDJUBJUB_ERR_MALFORMED_SIGNATURE: i32 = 9;
+/// See [`Error::SelfTestFailed`].
+pub const REDJUBJUB_ERR_SELF_TEST_FAILED: i32 = 10;
+/// See [`Error::IdentityKey`].
+pub const REDJUBJUB_ERR_IDENTITY_KEY: i32 = 11;
+
+fn error_code(e: Error) -> i32 {
+    match e {
+        Error::MalformedSigningKey => REDJUBJUB_ERR_MALFORMED_SIGNING_KEY,
+        Error::MalformedVerificationKey => REDJUBJUB_ERR_MALFORMED_VERIFICATION_KEY,
+        Error::MalformedRandomizer => REDJUBJUB_ERR_MALFORMED_RANDOMIZER,
+        Error::InvalidSignature => REDJUBJUB_ERR_INVALID_SIGNATURE,
+        Error::WeakRandomness => REDJUBJUB_ERR_WEAK_RANDOMNESS,
+        Error::MalformedKeyTable => REDJUBJUB_ERR_MALFORMED_KEY_TABLE,
+        Error::MalformedSignature => REDJUBJUB_ERR_MALFORMED_SIGNATURE,
+        Error::SelfTestFailed => REDJUBJUB_ERR_SELF_TEST_FAILED,
+        Error::IdentityKey => REDJUBJUB_ERR_IDENTITY_KEY,
+    }
+}
+
+/// An error crossing the FFI boundary.
+enum FfiError {
+    InvalidArgument,
+    Redjubjub(Error),
+}
+
+impl From<Error> for FfiError {
+    fn from(e: Error) -> Self {
+        FfiError::Redjubjub(e)
+    }
+}
+
+/// Run `f`, converting its result and any panic into a return code.
+fn guard<F: FnOnce() -> Result<(), FfiError> + panic::UnwindSafe>(f: F) -> i32 {
+    match panic::catch_unwind(f) {
+        Ok(Ok(())) => REDJUBJUB_OK,
+        Ok(Err(FfiError::InvalidArgument)) => REDJUBJUB_ERR_INVALID_ARGUMENT,
+        Ok(Err(FfiError::Redjubjub(e))) => error_code(e),
+        Err(_) => REDJUBJUB_ERR_PANIC,
+    }
+}
+
+/// Read a fixed-size buffer.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for reads of `N` bytes.
+unsafe fn read<const N: usize>(ptr: *const u8) -> Result<[u8; N], FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::InvalidArgument);
+    }
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(slice::from_raw_parts(ptr, N));
+    Ok(bytes)
+}
+
+/// Borrow a message buffer, which may be null if `len` is zero.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes for the lifetime `'a`.
+unsafe fn read_msg<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], FfiError> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(FfiError::InvalidArgument),
+        (false, _) => Ok(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+/// Write a fixed-size buffer.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for writes of `N` bytes.
+unsafe fn write<const N: usize>(ptr: *mut u8, bytes: [u8; N]) -> Result<(), FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::InvalidArgument);
+    }
+    slice::from_raw_parts_mut(ptr, N).copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn new_signing_key<T: SigType>() -> [u8; 32] {
+    SigningKey::<T>::new(OsRng).into()
+}
+
+fn verification_key<T: SigType>(sk: [u8; 32]) -> Result<[u8; 32], Error> {
+    let sk = SigningKey::<T>::try_from(sk)?;
+    Ok(crate::VerificationKey::from(&sk).into())
+}
+
+fn sign<T: SigType>(sk: [u8; 32], msg: &[u8]) -> Result<[u8; 64], Error> {
+    let sk = SigningKey::<T>::try_from(sk)?;
+    Ok(sk.sign(OsRng, msg).into())
+}
+
+fn verify<T: SigType>(vk: [u8; 32], msg: &[u8], sig: [u8; 64]) -> Result<(), Error> {
+    crate::VerificationKey::<T>::try_from(vk)?.verify(msg, &Signature::from(sig))
+}
+
+/// Generate a new signing key, writing it to the 32-byte `sk_out`.
+///
+/// # Safety
+///
+/// `sk_out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_signing_key_generate(sig_type: u8, sk_out: *mut u8) -> i32 {
+    guard(|| {
+        let sk = match sig_type {
+            REDJUBJUB_BINDING => new_signing_key::<Binding>(),
+            REDJUBJUB_SPENDAUTH => new_signing_key::<SpendAuth>(),
+            _ => return Err(FfiError::InvalidArgument),
+        };
+        write(sk_out, sk)
+    })
+}
+
+/// Derive the verification key of the 32-byte signing key `sk`, writing it
+/// to the 32-byte `vk_out`.
+///
+/// # Safety
+///
+/// `sk` must be valid for reads and `vk_out` for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_verification_key(
+    sig_type: u8,
+    sk: *const u8,
+    vk_out: *mut u8,
+) -> i32 {
+    guard(|| {
+        let sk = read::<32>(sk)?;
+        let vk = match sig_type {
+            REDJUBJUB_BINDING => verification_key::<Binding>(sk)?,
+            REDJUBJUB_SPENDAUTH => verification_key::<SpendAuth>(sk)?,
+            _ => return Err(FfiError::InvalidArgument),
+        };
+        write(vk_out, vk)
+    })
+}
+
+/// Sign the `msg_len` bytes at `msg` with the 32-byte signing key `sk`,
+/// writing the signature to the 64-byte `sig_out`.
+///
+/// # Safety
+///
+/// `sk` must be valid for reads of 32 bytes, `msg` for reads of `msg_len`
+/// bytes (it may be null if `msg_len` is zero), and `sig_out` for writes of
+/// 64 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_sign(
+    sig_type: u8,
+    sk: *const u8,
+    msg: *const u8,
+    msg_len: usize,
+    sig_out: *mut u8,
+) -> i32 {
+    guard(|| {
+        let sk = read::<32>(sk)?;
+        let msg = read_msg(msg, msg_len)?;
+        let sig = match sig_type {
+            REDJUBJUB_BINDING => sign::<Binding>(sk, msg)?,
+            REDJUBJUB_SPENDAUTH => sign::<SpendAuth>(sk, msg)?,
+            _ => return Err(FfiError::InvalidArgument),
+        };
+        write(sig_out, sig)
+    })
+}
+
+/// Verify the 64-byte signature `sig` over the `msg_len` bytes at `msg` by
+/// the 32-byte verification key `vk`.
+///
+/// # Safety
+///
+/// `vk` must be valid for reads of 32 bytes, `msg` for reads of `msg_len`
+/// bytes (it may be null if `msg_len` is zero), and `sig` for reads of 64
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_verify(
+    sig_type: u8,
+    vk: *const u8,
+    msg: *const u8,
+    msg_len: usize,
+    sig: *const u8,
+) -> i32 {
+    guard(|| {
+        let vk = read::<32>(vk)?;
+        let msg = read_msg(msg, msg_len)?;
+        let sig = read::<64>(sig)?;
+        match sig_type {
+            REDJUBJUB_BINDING => verify::<Binding>(vk, msg, sig)?,
+            REDJUBJUB_SPENDAUTH => verify::<SpendAuth>(vk, msg, sig)?,
+            _ => return Err(FfiError::InvalidArgument),
+        };
+        Ok(())
+    })
+}
+
+/// Queue the `n` signatures of a batch, as laid out for
+/// [`redjubjub_batch_verify`].
+///
+/// # Safety
+///
+/// As for [`redjubjub_batch_verify`].
+unsafe fn queue_batch(
+    n: usize,
+    sig_types: *const u8,
+    vks: *const u8,
+    msgs: *const *const u8,
+    msg_lens: *const usize,
+    sigs: *const u8,
+) -> Result<batch::Verifier, FfiError> {
+    let mut verifier = batch::Verifier::with_capacity(n);
+    if n == 0 {
+        return Ok(verifier);
+    }
+    if [sig_types, vks, sigs].iter().any(|p| p.is_null()) || msgs.is_null() || msg_lens.is_null() {
+        return Err(FfiError::InvalidArgument);
+    }
+    let sig_types = slice::from_raw_parts(sig_types, n);
+    let msgs = slice::from_raw_parts(msgs, n);
+    let msg_lens = slice::from_raw_parts(msg_lens, n);
+
+    for i in 0..n {
+        let vk = read::<32>(vks.add(32 * i))?;
+        let sig = read::<64>(sigs.add(64 * i))?;
+        let msg = read_msg(msgs[i], msg_lens[i])?;
+        match sig_types[i] {
+            REDJUBJUB_BINDING => verifier.queue((
+                crate::VerificationKeyBytes::<Binding>::from(vk),
+                Signature::<Binding>::from(sig),
+                &msg,
+            )),
+            REDJUBJUB_SPENDAUTH => verifier.queue((
+                crate::VerificationKeyBytes::<SpendAuth>::from(vk),
+                Signature::<SpendAuth>::from(sig),
+                &msg,
+            )),
+            _ => return Err(FfiError::InvalidArgument),
+        }
+    }
+    Ok(verifier)
+}
+
+/// Batch-verify `n` signatures, returning [`REDJUBJUB_OK`] only if all of
+/// them are valid.
+///
+/// Signature `i` has type `sig_types[i]`, verification key
+/// `vks[32 * i..32 * (i + 1)]`, signature `sigs[64 * i..64 * (i + 1)]`, and
+/// message `msgs[i]` of length `msg_lens[i]`.
+///
+/// # Safety
+///
+/// `sig_types`, `msgs` and `msg_lens` must be valid for reads of `n`
+/// elements, `vks` for reads of `32 * n` bytes and `sigs` for reads of
+/// `64 * n` bytes, and each `msgs[i]` must be valid for reads of
+/// `msg_lens[i]` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_batch_verify(
+    n: usize,
+    sig_types: *const u8,
+    vks: *const u8,
+    msgs: *const *const u8,
+    msg_lens: *const usize,
+    sigs: *const u8,
+) -> i32 {
+    guard(|| {
+        let verifier = queue_batch(n, sig_types, vks, msgs, msg_lens, sigs)?;
+        Ok(verifier.verify(OsRng)?)
+    })
+}
+
+/// Batch-verify `n` signatures as [`redjubjub_batch_verify`] does, and if the
+/// batch fails, find the first signature that fails on its own.
+///
+/// On failure the index of that signature is written to `invalid_index_out`,
+/// unlike the other outputs of this API, and the returned code is that
+/// signature's error. Finding it costs about one single verification per
+/// signature.
+///
+/// # Safety
+///
+/// As for [`redjubjub_batch_verify`], and `invalid_index_out` must be valid
+/// for writes of one `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_batch_verify_find_invalid(
+    n: usize,
+    sig_types: *const u8,
+    vks: *const u8,
+    msgs: *const *const u8,
+    msg_lens: *const usize,
+    sigs: *const u8,
+    invalid_index_out: *mut usize,
+) -> i32 {
+    guard(|| {
+        if invalid_index_out.is_null() {
+            return Err(FfiError::InvalidArgument);
+        }
+        let verifier = queue_batch(n, sig_types, vks, msgs, msg_lens, sigs)?;
+        let result = verifier.clone().verify(OsRng);
+        if result.is_ok() {
+            return Ok(());
+        }
+        match verifier.verify_each() {
+            Err(item) => {
+                *invalid_index_out = item.index;
+                Err(item.error.into())
+            }
+            Ok(()) => Ok(result?),
+        }
+    })
+}
+
+/// Randomize the 32-byte `SpendAuth` signing key `sk` with the 32-byte
+/// `randomizer`, writing the result to the 32-byte `sk_out`.
+///
+/// # Safety
+///
+/// `sk` and `randomizer` must be valid for reads and `sk_out` for writes of
+/// 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_randomize_signing_key(
+    sk: *const u8,
+    randomizer: *const u8,
+    sk_out: *mut u8,
+) -> i32 {
+    guard(|| {
+        let sk = SigningKey::<SpendAuth>::try_from(read::<32>(sk)?)?;
+        let randomizer = Randomizer::try_from(read::<32>(randomizer)?)?;
+        write(sk_out, sk.randomize(&randomizer).into())
+    })
+}
+
+/// Randomize the 32-byte `SpendAuth` verification key `vk` with the 32-byte
+/// `randomizer`, writing the result to the 32-byte `vk_out`.
+///
+/// # Safety
+///
+/// `vk` and `randomizer` must be valid for reads and `vk_out` for writes of
+/// 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn redjubjub_randomize_verification_key(
+    vk: *const u8,
+    randomizer: *const u8,
+    vk_out: *mut u8,
+) -> i32 {
+    guard(|| {
+        let vk = crate::VerificationKey::<SpendAuth>::try_from(read::<32>(vk)?)?;
+        let randomizer = Randomizer::try_from(read::<32>(randomizer)?)?;
+        write(vk_out, vk.randomize(&randomizer).into())
+    })
+}
+
+/// Run [`crate::self_test`], returning [`REDJUBJUB_OK`] if the library
+/// computes the expected answers on this platform.
+#[no_mangle]
+pub extern "C" fn redjubjub_self_test() -> i32 {
+    guard(|| crate::self_test().map_err(FfiError::from))
+}
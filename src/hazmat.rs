@@ -0,0 +1,328 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Low-level building blocks for implementers of higher-level protocols.
+//!
+//! ⚠️ These are "hazardous materials": they are exposed so that other
+//! protocols can reproduce exactly what this crate computes internally (for
+//! instance, the challenge of a signature), and misusing them can break the
+//! security of those protocols. Ordinary signing and verification never
+//! needs anything in this module.
+//!
+//! [`Scalar`] and [`Point`] are opaque wrappers around the JubJub scalar
+//! field and curve, with just the arithmetic needed to compose protocols
+//! (tweaks, adaptor signatures, ...) on top of RedJubjub keys.
+//!
+//! With the `hazmat-test-utils` feature, `sign_with_commitment`,
+//! `Point::torsion` and `with_unreduced_s` build the non-canonical and
+//! torsioned signatures that consensus tests need to pin down what
+//! verification accepts. They have no use outside tests, so enable the
+//! feature only for dev-dependencies.
+
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use blake2b_simd::{Params, State};
+
+use crate::{encoding::Hex, Randomizer, SigType, VerificationKey};
+#[cfg(feature = "hazmat-test-utils")]
+use crate::{Signature, SigningKey};
+
+pub use crate::params::H_STAR_PERSONALIZATION;
+
+/// Provides `H^star`, the hash-to-scalar function used by RedJubjub.
+///
+/// The challenge of a signature `(R, S)` by the verification key `vk` on the
+/// message `M` is `H^star(R || vk || M)`, with all points in their 32-byte
+/// encodings.
+#[derive(Clone)]
+pub struct HStar {
+    state: State,
+}
+
+impl Default for HStar {
+    fn default() -> Self {
+        Self::with_personalization(H_STAR_PERSONALIZATION)
+    }
+}
+
+impl HStar {
+    /// Construct the hash function with a BLAKE2b personalization other than
+    /// [`H_STAR_PERSONALIZATION`], for domain separation in other protocols.
+    pub fn with_personalization(personalization: &[u8; 16]) -> Self {
+        let state = Params::new()
+            .hash_length(64)
+            .personal(personalization)
+            .to_state();
+        Self { state }
+    }
+
+    /// Add `data` to the hash, and return `Self` for chaining.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) -> &mut Self {
+        self.state.update(data.as_ref());
+        self
+    }
+
+    /// Compute the hash output: the 64-byte BLAKE2b digest reduced modulo the
+    /// JubJub scalar field order.
+    pub fn finalize(&self) -> Scalar {
+        Scalar::from_bytes_wide(self.state.finalize().as_array())
+    }
+}
+
+/// Hash `data` to a scalar, using `domain` as the BLAKE2b personalization.
+///
+/// This is shorthand for
+/// `HStar::with_personalization(domain).update(data).finalize()`; pass
+/// [`H_STAR_PERSONALIZATION`] to get RedJubjub's own `H^star`.
+pub fn hash_to_scalar(domain: &[u8; 16], data: &[u8]) -> Scalar {
+    HStar::with_personalization(domain).update(data).finalize()
+}
+
+/// An element of the JubJub scalar field.
+///
+/// The `Debug` output is redacted, since scalars are often secret.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar(jubjub::Scalar);
+
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Scalar").field(&"<redacted>").finish()
+    }
+}
+
+impl Scalar {
+    /// The additive identity.
+    pub fn zero() -> Scalar {
+        Scalar(jubjub::Scalar::zero())
+    }
+
+    /// The multiplicative identity.
+    pub fn one() -> Scalar {
+        Scalar(jubjub::Scalar::one())
+    }
+
+    /// Parse a canonical little-endian encoding, returning `None` if `bytes`
+    /// is not less than the field order.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+        Option::from(jubjub::Scalar::from_bytes(bytes)).map(Scalar)
+    }
+
+    /// Reduce 64 little-endian bytes modulo the field order.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Scalar {
+        Scalar(jubjub::Scalar::from_bytes_wide(bytes))
+    }
+
+    /// The canonical little-endian encoding of this scalar.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// The multiplicative inverse, or `None` for zero.
+    pub fn invert(&self) -> Option<Scalar> {
+        Option::from(self.0.invert()).map(Scalar)
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+    fn add(self, other: Scalar) -> Scalar {
+        Scalar(self.0 + other.0)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+    fn sub(self, other: Scalar) -> Scalar {
+        Scalar(self.0 - other.0)
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+    fn mul(self, other: Scalar) -> Scalar {
+        Scalar(self.0 * other.0)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        Scalar(-self.0)
+    }
+}
+
+impl From<Randomizer> for Scalar {
+    fn from(randomizer: Randomizer) -> Scalar {
+        Scalar(randomizer.0)
+    }
+}
+
+impl From<Scalar> for Randomizer {
+    fn from(scalar: Scalar) -> Randomizer {
+        Randomizer(scalar.0)
+    }
+}
+
+/// A point on the JubJub curve.
+///
+/// Points are not required to be in the prime-order subgroup, matching the
+/// verification keys accepted by this crate.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Point(jubjub::ExtendedPoint);
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Point")
+            .field(&format_args!("{}", Hex(&self.to_bytes())))
+            .finish()
+    }
+}
+
+impl Point {
+    /// The identity point.
+    pub fn identity() -> Point {
+        Point(jubjub::ExtendedPoint::identity())
+    }
+
+    /// The basepoint for the signature type `T`, so that the verification
+    /// key of the signing key `sk` is `Point::basepoint::<T>() * sk`.
+    pub fn basepoint<T: SigType>() -> Point {
+        Point::from_bytes(&<T as crate::private::Sealed>::BASEPOINT_BYTES)
+            .expect("basepoint encodings are valid")
+    }
+
+    /// Parse a point from its 32-byte encoding, returning `None` if it is not
+    /// the canonical encoding of a curve point.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Point> {
+        Option::from(jubjub::AffinePoint::from_bytes(*bytes))
+            .map(|point: jubjub::AffinePoint| Point(point.into()))
+    }
+
+    /// The 32-byte encoding of this point.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        jubjub::AffinePoint::from(self.0).to_bytes()
+    }
+
+    /// The point `[k]T`, where `T` is a fixed generator of the small-order
+    /// subgroup, of order 8.
+    ///
+    /// `k = 0` gives the identity and `k = 4` the point of order 2; other even
+    /// `k` give points of order 4, and odd `k` points of order 8.
+    #[cfg(feature = "hazmat-test-utils")]
+    pub fn torsion(k: u8) -> Point {
+        // The point G with v = 11 and even u, the curve generator used by the
+        // `jubjub` crate, has order 8r, so [r]G generates the torsion
+        // subgroup; compute it as [r - 1]G + G.
+        let mut g_bytes = [0; 32];
+        g_bytes[0] = 11;
+        let g = Point::from_bytes(&g_bytes)
+            .expect("v = 11 is on the curve")
+            .0;
+        let t = g * -jubjub::Scalar::one() + g;
+        Point(t * jubjub::Scalar::from(u64::from(k % 8)))
+    }
+
+    /// Returns whether this point has small order, including the identity.
+    pub fn is_small_order(&self) -> bool {
+        self.0.is_small_order().into()
+    }
+
+    /// Returns whether this point is in the prime-order subgroup.
+    pub fn is_torsion_free(&self) -> bool {
+        self.0.is_torsion_free().into()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 - other.0)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point(-self.0)
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Point;
+    fn mul(self, scalar: Scalar) -> Point {
+        Point(self.0 * scalar.0)
+    }
+}
+
+impl<T: SigType> From<VerificationKey<T>> for Point {
+    fn from(vk: VerificationKey<T>) -> Point {
+        Point::from_bytes(&vk.to_bytes()).expect("verification keys are valid points")
+    }
+}
+
+/// Sign `msg` with `sk`, using the given `nonce` and committing to
+/// `commitment` in place of `[nonce]B`.
+///
+/// With `commitment = Point::basepoint::<T>() * nonce` this is an ordinary
+/// signature. Adding a [`Point::torsion`] component to the commitment gives
+/// a signature that only the cofactored verification equation accepts.
+///
+/// ⚠️ This is for consensus tests only: reusing a nonce, or choosing it in
+/// any way other than uniformly at random, reveals the signing key.
+#[cfg(feature = "hazmat-test-utils")]
+pub fn sign_with_commitment<T: SigType>(
+    sk: &SigningKey<T>,
+    nonce: Scalar,
+    commitment: Point,
+    msg: &[u8],
+) -> Signature<T> {
+    let vk = VerificationKey::from(sk);
+    let sk = Scalar::from_bytes(&sk.to_bytes()).expect("signing keys are canonical scalars");
+    let r_bytes = commitment.to_bytes();
+    let c = HStar::default()
+        .update(r_bytes)
+        .update(vk.to_bytes())
+        .update(msg)
+        .finalize();
+    let mut sig = [0; 64];
+    sig[..32].copy_from_slice(&r_bytes);
+    sig[32..].copy_from_slice(&(nonce + c * sk).to_bytes());
+    sig.into()
+}
+
+/// Add the scalar field order to the `s` component of `sig`, giving an
+/// encoding that is equal to `s` modulo the order but not canonical.
+///
+/// Verification must reject the result. This is for consensus tests only.
+#[cfg(feature = "hazmat-test-utils")]
+pub fn with_unreduced_s<T: SigType>(sig: &Signature<T>) -> Signature<T> {
+    // The field order is one more than the encoding of -1.
+    let order_minus_one = (-Scalar::one()).to_bytes();
+    let mut s = sig.s_bytes();
+    let mut carry = 1u16;
+    for (s, l) in s.iter_mut().zip(order_minus_one.iter()) {
+        let sum = u16::from(*s) + u16::from(*l) + carry;
+        *s = sum as u8;
+        carry = sum >> 8;
+    }
+    // `s` and the order are both below 2^252, so their sum fits.
+    debug_assert_eq!(carry, 0);
+
+    let mut bytes = [0; 64];
+    bytes[..32].copy_from_slice(&sig.r_bytes());
+    bytes[32..].copy_from_slice(&s);
+    bytes.into()
+}
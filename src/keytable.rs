@@ -0,0 +1,222 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! A compact, zero-copy table of verification keys.
+//!
+//! Services that keep millions of keys can store them in the [`KeyTable`]
+//! format and load them without parsing: a table borrows its bytes, so they
+//! can come straight from a memory-mapped file (for instance, one mapped with
+//! the `memmap2` crate). Keys are only decoded and validated when first used;
+//! the decoded key, or the rejection, is then cached, so later lookups do not
+//! decompress the point again.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Offset | Size       | Contents                                        |
+//! |--------|------------|-------------------------------------------------|
+//! | 0      | 4          | The magic bytes `RJKT`                          |
+//! | 4      | 1          | The format version, `1`                         |
+//! | 5      | 1          | The signature type: `0` for Binding, `1` for SpendAuth |
+//! | 6      | 2          | Reserved, must be zero                          |
+//! | 8      | 8          | The number of keys `n`, as a `u64`              |
+//! | 16     | `32 * n`   | The key encodings, back to back                 |
+
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{PoisonError, RwLock},
+    vec::Vec,
+};
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{batch, Error, SigType, Signature, VerificationKey, VerificationKeyBytes};
+
+const MAGIC: &[u8; 4] = b"RJKT";
+const VERSION: u8 = crate::build_info::KEY_TABLE_VERSION;
+const HEADER_LEN: usize = 16;
+
+/// The number of independently locked parts of the cache.
+const SHARDS: usize = 16;
+
+/// The cached results of decoding the keys used so far, by index.
+type Shard<T> = RwLock<HashMap<usize, Result<VerificationKey<T>, Error>>>;
+
+/// A table of verification keys of type `T`, borrowed from its serialized
+/// bytes.
+///
+/// Lookups are by index. Decoded keys are cached in a map split into
+/// separately locked shards, so the table can be shared between threads.
+/// Loading a table allocates nothing per key: the cache only grows, by about
+/// 200 bytes, for each key that is actually used.
+pub struct KeyTable<'a, T: SigType> {
+    keys: &'a [u8],
+    len: usize,
+    cache: [Shard<T>; SHARDS],
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: SigType> KeyTable<'a, T> {
+    /// Borrow a table from its serialized `bytes`.
+    ///
+    /// Only the header and length are checked here; the keys themselves are
+    /// validated on first use. Returns [`Error::MalformedKeyTable`] if the
+    /// header is invalid, is for the other signature type, or does not match
+    /// the length of `bytes`.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN
+            || &bytes[0..4] != MAGIC
+            || bytes[4] != VERSION
+            || bytes[5] != <T as crate::private::Sealed>::TAG
+            || bytes[6..8] != [0, 0]
+        {
+            return Err(Error::MalformedKeyTable);
+        }
+        let mut count = [0; 8];
+        count.copy_from_slice(&bytes[8..16]);
+        let count =
+            usize::try_from(u64::from_le_bytes(count)).map_err(|_| Error::MalformedKeyTable)?;
+        let keys = &bytes[HEADER_LEN..];
+        if count.checked_mul(32) != Some(keys.len()) {
+            return Err(Error::MalformedKeyTable);
+        }
+        Ok(Self {
+            keys,
+            len: count,
+            cache: core::array::from_fn(|_| RwLock::new(HashMap::new())),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Serialize `keys` in the table format.
+    pub fn encode<I>(keys: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = VerificationKeyBytes<T>>,
+    {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&[VERSION, <T as crate::private::Sealed>::TAG, 0, 0]);
+        bytes.extend_from_slice(&[0; 8]);
+        let mut count = 0u64;
+        for key in keys {
+            bytes.extend_from_slice(&key.to_bytes());
+            count += 1;
+        }
+        bytes[8..16].copy_from_slice(&count.to_le_bytes());
+        bytes
+    }
+
+    /// The number of keys in the table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the table holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The encoding of the key at `index`, without validating it.
+    pub fn get_bytes(&self, index: usize) -> Option<VerificationKeyBytes<T>> {
+        let bytes = self.keys.get(32 * index..32 * (index + 1))?;
+        let mut key = [0; 32];
+        key.copy_from_slice(bytes);
+        Some(key.into())
+    }
+
+    /// The key at `index`, or `None` if `index` is out of range.
+    ///
+    /// Returns `Some(Err(Error::MalformedVerificationKey))` if the key is
+    /// invalid. The key is only decoded on its first lookup; later lookups
+    /// return the cached result.
+    pub fn get(&self, index: usize) -> Option<Result<VerificationKey<T>, Error>> {
+        let bytes = self.get_bytes(index)?;
+        let shard = &self.cache[index % SHARDS];
+        if let Some(result) = shard
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&index)
+        {
+            return Some(*result);
+        }
+        let result = VerificationKey::try_from(bytes);
+        shard
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(index, result);
+        Some(result)
+    }
+
+    /// Check that every key in the table is valid, returning the index of the
+    /// first invalid key otherwise.
+    pub fn validate_all(&self) -> Result<(), usize> {
+        for index in 0..self.len() {
+            if let Some(Err(_)) = self.get(index) {
+                return Err(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify `sig` over `msg` by the key at `index`.
+    ///
+    /// Fails with [`Error::MalformedVerificationKey`] if `index` is out of
+    /// range or the key is invalid.
+    pub fn verify(&self, index: usize, msg: &[u8], sig: &Signature<T>) -> Result<(), Error> {
+        self.get(index)
+            .ok_or(Error::MalformedVerificationKey)??
+            .verify(msg, sig)
+    }
+
+    /// Batch-verify signatures `(index, msg, sig)` against keys in the table,
+    /// returning `Ok(())` only if all of them are valid.
+    ///
+    /// Each key is looked up with [`KeyTable::get`] as its signature is
+    /// queued, so it is decoded and cached on first use like any other
+    /// lookup. Invalid keys and out-of-range indices fail the batch with
+    /// [`Error::MalformedVerificationKey`] before any signature is checked.
+    pub fn batch_verify<'m, I, R>(&self, signatures: I, rng: R) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (usize, &'m [u8], Signature<T>)>,
+        R: RngCore + CryptoRng,
+    {
+        let mut verifier = batch::Verifier::new();
+        for (index, msg, sig) in signatures {
+            let vk = self.get(index).ok_or(Error::MalformedVerificationKey)??;
+            verifier.queue_from(&Entry {
+                vk_bytes: vk.into(),
+                sig,
+                msg,
+            });
+        }
+        verifier.verify(rng)
+    }
+}
+
+/// A signature queued by [`KeyTable::batch_verify`].
+struct Entry<'m, T: SigType> {
+    vk_bytes: VerificationKeyBytes<T>,
+    sig: Signature<T>,
+    msg: &'m [u8],
+}
+
+impl<T: SigType> batch::BatchItemSource for Entry<'_, T> {
+    type SigType = T;
+
+    fn vk_bytes(&self) -> VerificationKeyBytes<T> {
+        self.vk_bytes
+    }
+
+    fn signature(&self) -> Signature<T> {
+        self.sig
+    }
+
+    fn payload(&self) -> &[u8] {
+        self.msg
+    }
+}
@@ -12,24 +12,45 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
 pub mod batch;
+mod build_info;
+pub mod encoding;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hazmat;
+#[cfg(feature = "std")]
+pub mod keytable;
+pub mod params;
+mod randomizer;
+#[cfg(feature = "std")]
+pub mod reverify;
+mod rng;
+mod scalar_mul;
+mod self_test;
 pub(crate) mod signature;
 mod signing_key;
 mod verification_key;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 use reddsa::sapling;
 
-/// An element of the JubJub scalar field used for randomization of public and secret keys.
-pub type Randomizer = reddsa::Randomizer<sapling::SpendAuth>;
-
+pub use crate::signature::Signature;
+pub use build_info::{build_info, BuildInfo};
 pub use error::Error;
-pub use signature::Signature;
-pub use signing_key::SigningKey;
-pub use verification_key::{VerificationKey, VerificationKeyBytes};
+pub use randomizer::{Randomizer, RandomizerSeed};
+pub use self_test::self_test;
+#[cfg(feature = "secrecy")]
+pub use signing_key::SecretSigningKey;
+pub use signing_key::{RandomizedSigningKey, SigningKey};
+pub use verification_key::{
+    verify, PreparedVerificationKey, VerificationKey, VerificationKeyBytes,
+};
 
 /// Abstracts over different RedJubJub parameter choices, [`Binding`]
 /// and [`SpendAuth`].
@@ -59,11 +80,70 @@ pub(crate) mod private {
     use super::*;
     pub trait Sealed: Copy + Clone + Eq + PartialEq + core::fmt::Debug {
         type RedDSASigType: reddsa::SigType;
+        /// A byte distinguishing the signature types in hashed encodings.
+        const TAG: u8;
+        /// The encoding of the basepoint for this signature type.
+        const BASEPOINT_BYTES: [u8; 32];
+        /// Create a batch verification item for a signature of this type.
+        fn batch_item(
+            vk_bytes: reddsa::VerificationKeyBytes<Self::RedDSASigType>,
+            sig: reddsa::Signature<Self::RedDSASigType>,
+            msg: &[u8],
+        ) -> reddsa::batch::Item<sapling::SpendAuth, sapling::Binding>;
     }
     impl Sealed for Binding {
         type RedDSASigType = sapling::Binding;
+        const TAG: u8 = 0;
+        const BASEPOINT_BYTES: [u8; 32] = params::BINDINGSIG_BASEPOINT_BYTES;
+        fn batch_item(
+            vk_bytes: reddsa::VerificationKeyBytes<Self::RedDSASigType>,
+            sig: reddsa::Signature<Self::RedDSASigType>,
+            msg: &[u8],
+        ) -> reddsa::batch::Item<sapling::SpendAuth, sapling::Binding> {
+            reddsa::batch::Item::from_binding(vk_bytes, sig, &msg)
+        }
     }
     impl Sealed for SpendAuth {
         type RedDSASigType = sapling::SpendAuth;
+        const TAG: u8 = 1;
+        const BASEPOINT_BYTES: [u8; 32] = params::SPENDAUTHSIG_BASEPOINT_BYTES;
+        fn batch_item(
+            vk_bytes: reddsa::VerificationKeyBytes<Self::RedDSASigType>,
+            sig: reddsa::Signature<Self::RedDSASigType>,
+            msg: &[u8],
+        ) -> reddsa::batch::Item<sapling::SpendAuth, sapling::Binding> {
+            reddsa::batch::Item::from_spendauth(vk_bytes, sig, &msg)
+        }
     }
 }
+
+/// Compile-time checks that the public types are `Send + Sync + 'static`,
+/// as promised in the crate documentation.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    assert_send_sync::<Signature<Binding>>();
+    assert_send_sync::<Signature<SpendAuth>>();
+    assert_send_sync::<SigningKey<Binding>>();
+    assert_send_sync::<SigningKey<SpendAuth>>();
+    assert_send_sync::<VerificationKey<Binding>>();
+    assert_send_sync::<VerificationKey<SpendAuth>>();
+    assert_send_sync::<VerificationKeyBytes<Binding>>();
+    assert_send_sync::<VerificationKeyBytes<SpendAuth>>();
+    assert_send_sync::<PreparedVerificationKey<Binding>>();
+    assert_send_sync::<PreparedVerificationKey<SpendAuth>>();
+    assert_send_sync::<Randomizer>();
+    assert_send_sync::<RandomizerSeed>();
+    assert_send_sync::<RandomizedSigningKey>();
+    assert_send_sync::<Error>();
+    assert_send_sync::<BuildInfo>();
+    assert_send_sync::<batch::Item>();
+    assert_send_sync::<batch::BatchStats>();
+    assert_send_sync::<batch::ItemError>();
+    assert_send_sync::<batch::KeyGroupedVerifier<SpendAuth>>();
+    assert_send_sync::<batch::Verifier>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<reverify::ReverifyGuard>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<keytable::KeyTable<'static, SpendAuth>>();
+};
@@ -0,0 +1,46 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! The parameters of RedJubjub as instantiated in Zcash, for implementers of
+//! compatible signers in other languages.
+//!
+//! These are the values given in [§5.4.7 of the Zcash protocol
+//! specification][concretereddsa]: the hash-to-scalar personalization shared
+//! by both signature types, and the basepoints that distinguish `SpendAuthSig`
+//! from `BindingSig`.
+//!
+//! [concretereddsa]: https://zips.z.cash/protocol/protocol.pdf#concretereddsa
+
+/// The length in bytes of signing key, verification key and randomizer
+/// encodings.
+pub const KEY_LENGTH: usize = 32;
+
+/// The length in bytes of signature encodings: `R` followed by `S`.
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// The BLAKE2b-512 personalization of `H^star`, the hash-to-scalar function,
+/// for both `BindingSig` and `SpendAuthSig`.
+pub const H_STAR_PERSONALIZATION: &[u8; 16] = b"Zcash_RedJubjubH";
+
+/// The BLAKE2s personalization from which the `SpendAuthSig` basepoint
+/// `G^Sapling` is derived, as `FindGroupHash^J*("Zcash_G_", "")`.
+pub const SPENDAUTHSIG_BASEPOINT_PERSONALIZATION: &[u8; 8] = b"Zcash_G_";
+
+/// The BLAKE2s personalization from which the `BindingSig` basepoint
+/// `R^Sapling` is derived, as `FindGroupHash^J*("Zcash_cv", "r")`.
+pub const BINDINGSIG_BASEPOINT_PERSONALIZATION: &[u8; 8] = b"Zcash_cv";
+
+/// The encoding of the `SpendAuthSig` basepoint `G^Sapling`.
+pub const SPENDAUTHSIG_BASEPOINT_BYTES: [u8; 32] = [
+    48, 181, 242, 170, 173, 50, 86, 48, 188, 221, 219, 206, 77, 103, 101, 109, 5, 253, 28, 194,
+    208, 55, 187, 83, 117, 182, 233, 109, 158, 1, 161, 215,
+];
+
+/// The encoding of the `BindingSig` basepoint `R^Sapling`.
+pub const BINDINGSIG_BASEPOINT_BYTES: [u8; 32] = [
+    139, 106, 11, 56, 185, 250, 174, 60, 59, 128, 59, 71, 176, 241, 70, 173, 80, 171, 34, 30, 110,
+    42, 251, 230, 219, 222, 69, 203, 169, 211, 129, 237,
+];
@@ -0,0 +1,182 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+use core::{convert::TryFrom, fmt};
+
+use rand_core::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{sapling, Error};
+
+/// An element of the JubJub scalar field used for randomization of public and
+/// secret keys.
+///
+/// In Zcash Sapling this is the spend authorization randomizer `alpha`, which
+/// must be applied consistently to [`SigningKey::randomize`] and
+/// [`VerificationKey::randomize`].
+///
+/// Randomizers are secret until the transaction using them is published, so
+/// the `Debug` output is redacted. With a human-readable serde format (such
+/// as JSON) a randomizer is encoded as a hex string; binary formats use the
+/// 32-byte encoding. Deserialization rejects non-canonical encodings.
+///
+/// Randomizers are `Copy`, so clearing one copy would not clear the others,
+/// and `Randomizer` does not implement `Zeroize`. To keep randomizers secret
+/// at rest, store a [`RandomizerSeed`], which is zeroized on drop, and derive
+/// them from it when needed.
+///
+/// [`SigningKey::randomize`]: crate::SigningKey::randomize
+/// [`VerificationKey::randomize`]: crate::VerificationKey::randomize
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Randomizer(pub(crate) reddsa::Randomizer<sapling::SpendAuth>);
+
+impl fmt::Debug for Randomizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Randomizer").field(&"<redacted>").finish()
+    }
+}
+
+impl Randomizer {
+    /// Generate a new, uniformly random randomizer.
+    pub fn new<R: RngCore + CryptoRng>(mut rng: R) -> Randomizer {
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+        Randomizer::from_bytes_wide(&bytes)
+    }
+
+    /// Reduce 64 bytes (for instance, a hash output) modulo the JubJub scalar
+    /// field order to obtain a randomizer.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Randomizer {
+        Randomizer(reddsa::Randomizer::<sapling::SpendAuth>::from_bytes_wide(
+            bytes,
+        ))
+    }
+
+    /// Parse a canonical little-endian encoding of a randomizer.
+    ///
+    /// Returns [`Error::MalformedRandomizer`] if the bytes are not less than
+    /// the scalar field order.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Randomizer, Error> {
+        Randomizer::try_from(*bytes)
+    }
+
+    /// The canonical little-endian encoding of this randomizer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Randomizer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&crate::encoding::Hex(&self.to_bytes()))
+        } else {
+            self.to_bytes().serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Randomizer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = if deserializer.is_human_readable() {
+            crate::encoding::deserialize_hex::<_, 32>(deserializer)?
+        } else {
+            <[u8; 32]>::deserialize(deserializer)?
+        };
+        Randomizer::try_from(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A secret per-transaction seed from which the randomizers of all of its
+/// spends are derived.
+///
+/// A wallet or hardware device that keeps only the seed can reproduce every
+/// spend's randomizer with [`RandomizerSeed::derive`]. The seed is zeroized
+/// when dropped, and the `Debug` output is redacted.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct RandomizerSeed([u8; 32]);
+
+impl fmt::Debug for RandomizerSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RandomizerSeed")
+            .field(&"<redacted>")
+            .finish()
+    }
+}
+
+impl From<[u8; 32]> for RandomizerSeed {
+    fn from(bytes: [u8; 32]) -> RandomizerSeed {
+        RandomizerSeed(bytes)
+    }
+}
+
+impl RandomizerSeed {
+    /// Generate a new, uniformly random seed.
+    pub fn new<R: RngCore + CryptoRng>(mut rng: R) -> RandomizerSeed {
+        let mut bytes = [0; 32];
+        rng.fill_bytes(&mut bytes);
+        RandomizerSeed(bytes)
+    }
+
+    /// The 32 bytes of this seed.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Derive the randomizer for the spend at `index`.
+    ///
+    /// The randomizer is the scalar
+    ///
+    /// ```text
+    /// BLAKE2b-512("RedJubjubRandDrv", seed || index) mod r
+    /// ```
+    ///
+    /// where the personalization is the 16 bytes `RedJubjubRandDrv`, `index`
+    /// is a little-endian `u64`, and `r` is the JubJub scalar field order.
+    /// This derivation will not change between versions.
+    pub fn derive(&self, index: u64) -> Randomizer {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(b"RedJubjubRandDrv")
+            .to_state()
+            .update(&self.0)
+            .update(&index.to_le_bytes())
+            .finalize();
+        Randomizer::from_bytes_wide(hash.as_array())
+    }
+}
+
+impl From<Randomizer> for [u8; 32] {
+    fn from(randomizer: Randomizer) -> [u8; 32] {
+        randomizer.to_bytes()
+    }
+}
+
+impl TryFrom<[u8; 32]> for Randomizer {
+    type Error = Error;
+
+    /// Parse a canonical little-endian encoding of a randomizer.
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        Option::from(reddsa::Randomizer::<sapling::SpendAuth>::from_bytes(&bytes))
+            .map(Randomizer)
+            .ok_or(Error::MalformedRandomizer)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Randomizer {
+    type Error = Error;
+
+    /// Parse a randomizer from a slice, returning
+    /// [`Error::MalformedRandomizer`] if it is not a 32-byte canonical
+    /// encoding.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; 32]>::try_from(bytes)
+            .map_err(|_| Error::MalformedRandomizer)?
+            .try_into()
+    }
+}
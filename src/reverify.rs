@@ -0,0 +1,138 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Skips re-verification of signatures that are already known to be valid.
+//!
+//! Block template builders and mempools repeatedly verify the same
+//! signatures. A [`ReverifyGuard`] remembers which `(vk, sig, msg)` triples
+//! have been verified, and under which *generation* (for instance, which
+//! template rebuild), so that later passes only pay for a hash lookup.
+
+use std::collections::HashMap;
+
+use blake2b_simd::Params;
+
+use crate::{Error, SigType, Signature, VerificationKey, VerificationKeyBytes};
+
+/// A digest identifying a `(vk, sig, msg)` triple of some signature type.
+type Key = [u8; 32];
+
+/// A cache of successfully verified signatures, tagged with generation counters.
+///
+/// Only successful verifications are remembered, so the guard can never cause
+/// an invalid signature to be accepted. Entries are keyed by a
+/// collision-resistant BLAKE2b-256 digest, so the guard does not retain
+/// messages.
+#[derive(Clone, Debug, Default)]
+pub struct ReverifyGuard {
+    generation: u64,
+    verified: HashMap<Key, u64>,
+}
+
+impl ReverifyGuard {
+    /// Construct a new, empty guard at generation `0`.
+    pub fn new() -> ReverifyGuard {
+        ReverifyGuard::default()
+    }
+
+    /// The current generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Start a new generation, returning its number.
+    ///
+    /// Existing entries stay valid; they keep the generation in which they
+    /// were last verified or confirmed.
+    pub fn advance_generation(&mut self) -> u64 {
+        self.generation += 1;
+        self.generation
+    }
+
+    /// The number of remembered signatures.
+    pub fn len(&self) -> usize {
+        self.verified.len()
+    }
+
+    /// Returns `true` if no signatures are remembered.
+    pub fn is_empty(&self) -> bool {
+        self.verified.is_empty()
+    }
+
+    /// Verify `sig` over `msg` by `vk_bytes`, skipping the cryptographic check
+    /// if this triple is already known to be valid.
+    ///
+    /// On success the triple is marked as verified under the current generation.
+    pub fn verify<T: SigType>(
+        &mut self,
+        vk_bytes: VerificationKeyBytes<T>,
+        sig: &Signature<T>,
+        msg: &[u8],
+    ) -> Result<(), Error> {
+        let key = Self::key(vk_bytes, sig, msg);
+        if let Some(generation) = self.verified.get_mut(&key) {
+            *generation = self.generation;
+            return Ok(());
+        }
+
+        VerificationKey::try_from(vk_bytes).and_then(|vk| vk.verify(msg, sig))?;
+        self.verified.insert(key, self.generation);
+        Ok(())
+    }
+
+    /// Returns the generation under which this triple was last verified, if
+    /// it is known to be valid.
+    pub fn verified_generation<T: SigType>(
+        &self,
+        vk_bytes: VerificationKeyBytes<T>,
+        sig: &Signature<T>,
+        msg: &[u8],
+    ) -> Option<u64> {
+        self.verified.get(&Self::key(vk_bytes, sig, msg)).copied()
+    }
+
+    /// Forget a single triple, returning `true` if it was remembered.
+    pub fn invalidate<T: SigType>(
+        &mut self,
+        vk_bytes: VerificationKeyBytes<T>,
+        sig: &Signature<T>,
+        msg: &[u8],
+    ) -> bool {
+        self.verified
+            .remove(&Self::key(vk_bytes, sig, msg))
+            .is_some()
+    }
+
+    /// Forget every triple that was last verified before `generation`,
+    /// returning how many were removed.
+    pub fn invalidate_before(&mut self, generation: u64) -> usize {
+        let before = self.verified.len();
+        self.verified.retain(|_, verified| *verified >= generation);
+        before - self.verified.len()
+    }
+
+    /// Forget every remembered triple. The generation counter is unchanged.
+    pub fn clear(&mut self) {
+        self.verified.clear();
+    }
+
+    fn key<T: SigType>(vk_bytes: VerificationKeyBytes<T>, sig: &Signature<T>, msg: &[u8]) -> Key {
+        let vk_bytes: [u8; 32] = vk_bytes.into();
+        let sig_bytes: [u8; 64] = (*sig).into();
+        let hash = Params::new()
+            .hash_length(32)
+            .personal(b"RedJubjubReverif")
+            .to_state()
+            .update(&[T::TAG])
+            .update(&vk_bytes[..])
+            .update(&sig_bytes[..])
+            .update(msg)
+            .finalize();
+        let mut key = [0; 32];
+        key.copy_from_slice(hash.as_bytes());
+        key
+    }
+}
@@ -0,0 +1,166 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! RNG adapters used where the library needs to control the randomness fed
+//! into `reddsa`: a deterministic RNG backed by BLAKE2b, and a wrapper that
+//! health-checks a caller-provided RNG.
+
+use blake2b_simd::{Params, State};
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// An RNG whose output stream is `BLAKE2b-512(seed || counter)` for
+/// `counter = 0, 1, ...`, where `seed` is everything passed to [`HashRng::update`].
+///
+/// This must only be seeded with secret, high-entropy material.
+pub(crate) struct HashRng {
+    state: State,
+    counter: u64,
+    block: [u8; 64],
+    offset: usize,
+}
+
+impl HashRng {
+    /// Create a new RNG using the given 16-byte BLAKE2b personalization.
+    pub(crate) fn new(personalization: &[u8; 16]) -> Self {
+        let state = Params::new()
+            .hash_length(64)
+            .personal(personalization)
+            .to_state();
+        Self {
+            state,
+            counter: 0,
+            block: [0; 64],
+            offset: 64,
+        }
+    }
+
+    /// Add `data` to the seed, and return `Self` for chaining.
+    ///
+    /// Must not be called after any output has been drawn.
+    pub(crate) fn update(mut self, data: &[u8]) -> Self {
+        debug_assert_eq!(self.counter, 0);
+        self.state.update(data);
+        self
+    }
+
+    fn refill(&mut self) {
+        let block = self
+            .state
+            .clone()
+            .update(&self.counter.to_le_bytes())
+            .finalize();
+        self.block.copy_from_slice(block.as_bytes());
+        self.counter += 1;
+        self.offset = 0;
+    }
+}
+
+impl RngCore for HashRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut written = 0;
+        while written < dest.len() {
+            if self.offset == self.block.len() {
+                self.refill();
+            }
+            let n = (dest.len() - written).min(self.block.len() - self.offset);
+            dest[written..written + n].copy_from_slice(&self.block[self.offset..self.offset + n]);
+            written += n;
+            self.offset += n;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for HashRng {}
+
+/// Wraps a caller-provided RNG, passing its output through unchanged while
+/// checking each draw for obvious failures.
+///
+/// These are heuristics for catching broken RNGs (stuck, unseeded or
+/// repeating output), not a statistical test of randomness.
+pub(crate) struct CheckedRng<R> {
+    rng: R,
+    healthy: bool,
+}
+
+impl<R: RngCore + CryptoRng> CheckedRng<R> {
+    pub(crate) fn new(rng: R) -> Self {
+        Self { rng, healthy: true }
+    }
+
+    /// Returns [`crate::Error::WeakRandomness`] if any draw so far failed a
+    /// check.
+    pub(crate) fn finish(self) -> Result<(), crate::Error> {
+        if self.healthy {
+            Ok(())
+        } else {
+            Err(crate::Error::WeakRandomness)
+        }
+    }
+}
+
+/// Check a single draw of at least 16 bytes. Shorter draws are not checked.
+fn looks_healthy(bytes: &[u8]) -> bool {
+    if bytes.len() < 16 {
+        return true;
+    }
+    // Constant or periodic output, e.g. all zeros from an unseeded source or a
+    // short buffer replayed in a loop.
+    if (1..=bytes.len() / 2).any(|period| bytes[period..] == bytes[..bytes.len() - period]) {
+        return false;
+    }
+    // Uniform draws of 32 or more bytes have many distinct byte values (about
+    // 57 for 64 bytes); fewer than a quarter of the length is vanishingly rare.
+    if bytes.len() >= 32 {
+        let mut seen = [false; 256];
+        for b in bytes {
+            seen[*b as usize] = true;
+        }
+        if seen.iter().filter(|s| **s).count() < bytes.len() / 4 {
+            return false;
+        }
+    }
+    true
+}
+
+impl<R: RngCore + CryptoRng> RngCore for CheckedRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        self.healthy &= looks_healthy(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)?;
+        self.healthy &= looks_healthy(dest);
+        Ok(())
+    }
+}
+
+impl<R: RngCore + CryptoRng> CryptoRng for CheckedRng<R> {}
@@ -0,0 +1,163 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! Fixed-base scalar multiplication with a precomputed table, for when many
+//! multiples of the same point are needed, and variable-time multiscalar
+//! multiplication for batch verification.
+
+use alloc::vec::Vec;
+
+use jubjub::{AffineNielsPoint, ExtendedNielsPoint, ExtendedPoint, Scalar};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+/// The multiples `k * 16^i * B`, for `k < 16` and `i < 64`, of a fixed point
+/// `B`, so that multiplying `B` by a scalar takes 64 additions and no
+/// doublings.
+///
+/// Building the table costs about as much as five ordinary scalar
+/// multiplications.
+pub(crate) struct FixedBaseTable(Vec<[AffineNielsPoint; 16]>);
+
+impl FixedBaseTable {
+    pub(crate) fn new(base: ExtendedPoint) -> Self {
+        let mut points = Vec::with_capacity(64 * 16);
+        let mut window_base = base;
+        for _ in 0..64 {
+            let mut multiple = ExtendedPoint::identity();
+            for _ in 0..16 {
+                points.push(multiple);
+                multiple += window_base;
+            }
+            // `multiple` is now `16 * window_base`.
+            window_base = multiple;
+        }
+
+        let affine: Vec<_> = jubjub::batch_normalize(&mut points).collect();
+        let windows = affine
+            .chunks(16)
+            .map(|chunk| {
+                let mut window = [AffineNielsPoint::identity(); 16];
+                for (entry, point) in window.iter_mut().zip(chunk) {
+                    *entry = point.to_niels();
+                }
+                window
+            })
+            .collect();
+        Self(windows)
+    }
+
+    /// Multiply the base by `scalar`, in constant time.
+    pub(crate) fn mul(&self, scalar: &Scalar) -> ExtendedPoint {
+        let bytes = scalar.to_bytes();
+        let mut acc = ExtendedPoint::identity();
+        for (i, window) in self.0.iter().enumerate() {
+            let nibble = (bytes[i / 2] >> (4 * (i % 2))) & 0x0f;
+            let mut selected = AffineNielsPoint::identity();
+            for (k, entry) in window.iter().enumerate() {
+                selected.conditional_assign(entry, (k as u8).ct_eq(&nibble));
+            }
+            acc += selected;
+        }
+        acc
+    }
+
+    /// Multiply the base by `scalar`, in variable time. Only use this for
+    /// public scalars, such as in signature verification.
+    pub(crate) fn mul_vartime(&self, scalar: &Scalar) -> ExtendedPoint {
+        let bytes = scalar.to_bytes();
+        let mut acc = ExtendedPoint::identity();
+        for (i, window) in self.0.iter().enumerate() {
+            let nibble = (bytes[i / 2] >> (4 * (i % 2))) & 0x0f;
+            if nibble != 0 {
+                acc += window[nibble as usize];
+            }
+        }
+        acc
+    }
+}
+
+/// The number of width-5 NAF digits of a JubJub scalar, whose order is about
+/// 2^251.85.
+const NAF_LENGTH: usize = 253;
+
+/// The width-5 non-adjacent form of `scalar`: signed, odd digits below 16 in
+/// absolute value, with at least four zeros between non-zero digits.
+fn non_adjacent_form(scalar: &Scalar) -> [i8; NAF_LENGTH] {
+    const W: usize = 5;
+    const WIDTH: u64 = 1 << W;
+
+    let bytes = scalar.to_bytes();
+    let mut limbs = [0u64; 5];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(word);
+    }
+
+    let mut naf = [0; NAF_LENGTH];
+    let mut pos = 0;
+    let mut carry = 0;
+    while pos < NAF_LENGTH {
+        let (limb, bit) = (pos / 64, pos % 64);
+        let bits = if bit < 64 - W {
+            limbs[limb] >> bit
+        } else {
+            (limbs[limb] >> bit) | (limbs[limb + 1] << (64 - bit))
+        };
+
+        let window = carry + (bits & (WIDTH - 1));
+        if window & 1 == 0 {
+            // An even window leaves the carry as it is.
+            pos += 1;
+            continue;
+        }
+        if window < WIDTH / 2 {
+            carry = 0;
+            naf[pos] = window as i8;
+        } else {
+            carry = 1;
+            naf[pos] = (window as i8).wrapping_sub(WIDTH as i8);
+        }
+        pos += W;
+    }
+    naf
+}
+
+/// Compute `sum(scalars[i] * points[i])` in variable time, using width-5 NAFs.
+/// Only use this for public data, such as in signature verification.
+pub(crate) fn vartime_multiscalar_mul(
+    scalars: &[Scalar],
+    points: &[ExtendedPoint],
+) -> ExtendedPoint {
+    debug_assert_eq!(scalars.len(), points.len());
+
+    let nafs: Vec<_> = scalars.iter().map(non_adjacent_form).collect();
+    // The odd multiples 1P, 3P, ..., 15P of each point.
+    let tables: Vec<[ExtendedNielsPoint; 8]> = points
+        .iter()
+        .map(|point| {
+            let mut table = [point.to_niels(); 8];
+            let double = point.double();
+            for i in 0..7 {
+                table[i + 1] = (double + table[i]).to_niels();
+            }
+            table
+        })
+        .collect();
+
+    let mut acc = ExtendedPoint::identity();
+    for i in (0..NAF_LENGTH).rev() {
+        acc = acc.double();
+        for (naf, table) in nafs.iter().zip(tables.iter()) {
+            match naf[i] {
+                0 => {}
+                digit if digit > 0 => acc += table[digit as usize / 2],
+                digit => acc -= table[(-digit) as usize / 2],
+            }
+        }
+    }
+    acc
+}
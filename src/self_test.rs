@@ -0,0 +1,72 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+use crate::{
+    batch, encoding, rng::HashRng, Binding, Error, Randomizer, SigningKey, SpendAuth,
+    VerificationKey,
+};
+
+// Known answers, matching `tests/fixtures/snapshots.txt`.
+const SPENDAUTH_SK: &str = "589d6fa89ac361a284c3855bdfb03ad83632d068805a3868077e005651073d04";
+const SPENDAUTH_VK: &str = "c412115904d2d2a05e9de5f4235405abb8b266f08908eb2f249e490a8c164d28";
+const SPENDAUTH_SIG: &str = "439a91c9a5b738fb74a9c256fecd3d671d4d3d6fe102055d08c9779c33d85a43\
+                             45ce6bcbdaef9db797acd4ad79955a1588d9f8b5f1826fd2ac8348296521ff00";
+const BINDING_VK: &str = "3f53f406593ff419fa5c9a73f8befde8e018731f046e0c570a7216d88fdb47a7";
+const RANDOMIZED_VK: &str = "5ee42cc43dabb0472578958ba1078dbd351cd3a759e0fc063848489f9bbfd072";
+
+/// All the known answers, by name, for [`crate::build_info`] to digest.
+pub(crate) const KNOWN_ANSWERS: [(&str, &str); 5] = [
+    ("spendauth.signing_key", SPENDAUTH_SK),
+    ("spendauth.verification_key", SPENDAUTH_VK),
+    ("spendauth.signature", SPENDAUTH_SIG),
+    ("binding.verification_key", BINDING_VK),
+    ("randomized_verification_key", RANDOMIZED_VK),
+];
+
+fn check(ok: bool) -> Result<(), Error> {
+    if ok {
+        Ok(())
+    } else {
+        Err(Error::SelfTestFailed)
+    }
+}
+
+/// Check key derivation, signing, verification, batch verification and key
+/// randomization against known answers.
+///
+/// The test suite already checks these on every target it runs on; this
+/// function is for applications deployed to platforms that upstream CI does
+/// not cover (such as 32-bit or big-endian systems), which can call it once at
+/// startup. It takes a few milliseconds and needs no RNG. Returns
+/// [`Error::SelfTestFailed`] if any answer is wrong.
+pub fn self_test() -> Result<(), Error> {
+    let hex_32 = |hex| encoding::decode_hex_32(hex).expect("known answers are valid hex");
+
+    let sk = SigningKey::<SpendAuth>::from_seed(&[0x42; 32], b"spendauth");
+    check(sk.to_bytes() == hex_32(SPENDAUTH_SK))?;
+    let vk = VerificationKey::from(&sk);
+    check(vk.to_bytes() == hex_32(SPENDAUTH_VK))?;
+
+    let msg = b"snapshot message";
+    let sig = sk.sign_deterministic(msg);
+    check(sig.to_bytes() == encoding::decode_hex_64(SPENDAUTH_SIG).expect("valid hex"))?;
+    vk.verify(msg, &sig)?;
+    check(vk.verify(b"other message", &sig).is_err())?;
+
+    let binding_sk = SigningKey::<Binding>::from_seed(&[0x42; 32], b"binding");
+    let binding_vk = VerificationKey::from(&binding_sk);
+    check(binding_vk.to_bytes() == hex_32(BINDING_VK))?;
+    let binding_sig = binding_sk.sign_deterministic(msg);
+
+    let mut verifier = batch::Verifier::new();
+    verifier.queue((vk.into(), sig, msg));
+    verifier.queue((binding_vk.into(), binding_sig, msg));
+    verifier.verify(HashRng::new(b"RedJubjubSelfTst"))?;
+
+    let ak = VerificationKey::from(&SigningKey::<SpendAuth>::from_seed(&[0x42; 32], b"ak"));
+    let randomizer = Randomizer::from_bytes_wide(&[0x17; 64]);
+    check(ak.randomize(&randomizer).to_bytes() == hex_32(RANDOMIZED_VK))
+}
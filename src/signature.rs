@@ -9,21 +9,161 @@
 
 //! Redjubjub Signatures
 
-use crate::SigType;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::{encoding::Hex, Error, SigType};
 
 /// A RedJubJub signature.
+///
+/// With a human-readable serde format (such as JSON) a signature is encoded as
+/// a hex string; binary formats use the compact 64-byte encoding.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Signature<T: SigType>(pub(crate) reddsa::Signature<T::RedDSASigType>);
 
+/// Hashed by the byte encoding.
+impl<T: SigType> Hash for Signature<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state)
+    }
+}
+
+/// Ordered by the byte encoding.
+impl<T: SigType> PartialOrd for Signature<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: SigType> Ord for Signature<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(&other.to_bytes())
+    }
+}
+
+impl<T: SigType> fmt::Display for Signature<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes: [u8; 64] = self.0.into();
+        Hex(&bytes).fmt(f)
+    }
+}
+
+/// Parses the hex encoding produced by `Display`, returning
+/// [`Error::MalformedSignature`] unless it is exactly 128 hex digits, of
+/// either case. As with [`Signature::from_bytes`], `R` and `s` are only
+/// checked during verification.
+impl<T: SigType> FromStr for Signature<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::encoding::decode_hex_64(s)
+            .map(Signature::from)
+            .ok_or(Error::MalformedSignature)
+    }
+}
+
 impl<T: SigType> From<[u8; 64]> for Signature<T> {
     fn from(bytes: [u8; 64]) -> Signature<T> {
         Signature(reddsa::Signature::<_>::from(bytes))
     }
 }
 
+impl<'a, T: SigType> TryFrom<&'a [u8]> for Signature<T> {
+    type Error = Error;
+
+    /// Parse a signature from a slice, returning
+    /// [`Error::MalformedSignature`] if it is not exactly 64 bytes long.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; 64]>::try_from(bytes)
+            .map(Signature::from)
+            .map_err(|_| Error::MalformedSignature)
+    }
+}
+
 impl<T: SigType> From<Signature<T>> for [u8; 64] {
     fn from(sig: Signature<T>) -> [u8; 64] {
         sig.0.into()
     }
 }
+
+impl<T: SigType> Signature<T> {
+    /// Parse a signature from its 64-byte encoding.
+    ///
+    /// This never fails: the encoding of `R` and `s` is checked during
+    /// verification.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Signature<T> {
+        Signature::from(*bytes)
+    }
+
+    /// The 64-byte encoding of this signature, `R` followed by `s`.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        (*self).into()
+    }
+
+    /// The encoding of the commitment point `R`, the first half of the
+    /// signature.
+    pub fn r_bytes(&self) -> [u8; 32] {
+        let mut r_bytes = [0; 32];
+        r_bytes.copy_from_slice(&self.to_bytes()[..32]);
+        r_bytes
+    }
+
+    /// The encoding of the response scalar `s`, the second half of the
+    /// signature.
+    pub fn s_bytes(&self) -> [u8; 32] {
+        let mut s_bytes = [0; 32];
+        s_bytes.copy_from_slice(&self.to_bytes()[32..]);
+        s_bytes
+    }
+
+    /// Assemble a signature from the encodings of `R` and `s`.
+    ///
+    /// Unlike [`Signature::from_bytes`], this checks the components up front,
+    /// returning [`Error::MalformedSignature`] unless `r_bytes` is the
+    /// canonical encoding of a curve point and `s_bytes` the canonical
+    /// encoding of a scalar.
+    pub fn from_parts(r_bytes: [u8; 32], s_bytes: [u8; 32]) -> Result<Signature<T>, Error> {
+        let r_valid = jubjub::AffinePoint::from_bytes(r_bytes).is_some();
+        let s_valid = jubjub::Scalar::from_bytes(&s_bytes).is_some();
+        if !bool::from(r_valid & s_valid) {
+            return Err(Error::MalformedSignature);
+        }
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&r_bytes);
+        bytes[32..].copy_from_slice(&s_bytes);
+        Ok(Signature::from(bytes))
+    }
+}
+
+/// The binary serde representation of a [`Signature`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Signature")]
+struct SerdeHelper<T: SigType>(reddsa::Signature<T::RedDSASigType>);
+
+#[cfg(feature = "serde")]
+impl<T: SigType> serde::Serialize for Signature<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            SerdeHelper::<T>(self.0).serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: SigType> serde::Deserialize<'de> for Signature<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            crate::encoding::deserialize_hex::<_, 64>(deserializer).map(Signature::from)
+        } else {
+            SerdeHelper::<T>::deserialize(deserializer).map(|helper| Signature(helper.0))
+        }
+    }
+}
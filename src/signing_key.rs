@@ -10,11 +10,20 @@
 
 use core::convert::{TryFrom, TryInto};
 
-use crate::{Error, Randomizer, SigType, Signature, SpendAuth, VerificationKey};
+use crate::{
+    rng::{CheckedRng, HashRng},
+    Binding, Error, Randomizer, SigType, Signature, SpendAuth, VerificationKey,
+};
 
 use rand_core::{CryptoRng, RngCore};
+use subtle::ConstantTimeEq;
 
 /// A RedJubJub signing key.
+///
+/// Signing keys are `Copy`, so any move may leave a copy behind, and clearing
+/// one copy would not clear the others. For that reason `SigningKey` does not
+/// implement `Zeroize`; with the `secrecy` feature, keep long-lived keys in a
+/// [`SecretSigningKey`] instead.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "SerdeHelper"))]
@@ -35,6 +44,71 @@ impl<T: SigType> From<SigningKey<T>> for [u8; 32] {
     }
 }
 
+/// A signing key held only as its encoding, in a [`secrecy::SecretBox`] that
+/// zeroizes it when dropped.
+///
+/// The byte buffer is not `Copy`, so the one long-lived copy of the key is in
+/// this box and is cleared by `zeroize`'s own implementation for byte arrays.
+/// [`SecretSigningKey::expose_secret`] decodes a temporary [`SigningKey`] for
+/// each use; since that is `Copy`, it is not zeroized. The `Debug` output is
+/// redacted.
+#[cfg(feature = "secrecy")]
+pub struct SecretSigningKey<T: SigType> {
+    bytes: secrecy::SecretBox<[u8; 32]>,
+    _marker: core::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "secrecy")]
+impl<T: SigType> SecretSigningKey<T> {
+    /// Decode the signing key, for signing or deriving its verification key.
+    ///
+    /// The returned key is an ordinary copy, which is not zeroized.
+    pub fn expose_secret(&self) -> SigningKey<T> {
+        use secrecy::ExposeSecret;
+        SigningKey::try_from(*self.bytes.expose_secret())
+            .expect("signing key encodings are canonical")
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<T: SigType> From<SigningKey<T>> for SecretSigningKey<T> {
+    fn from(sk: SigningKey<T>) -> SecretSigningKey<T> {
+        SecretSigningKey {
+            bytes: secrecy::SecretBox::init_with_mut(|bytes| *bytes = sk.to_bytes()),
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<T: SigType> Clone for SecretSigningKey<T> {
+    fn clone(&self) -> Self {
+        use secrecy::ExposeSecret;
+        SecretSigningKey {
+            bytes: secrecy::SecretBox::init_with_mut(|bytes: &mut [u8; 32]| {
+                bytes.copy_from_slice(self.bytes.expose_secret())
+            }),
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<T: SigType> core::fmt::Debug for SecretSigningKey<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SecretSigningKey")
+            .field(&"<redacted>")
+            .finish()
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<'a, T: SigType> From<&'a SecretSigningKey<T>> for VerificationKey<T> {
+    fn from(sk: &'a SecretSigningKey<T>) -> VerificationKey<T> {
+        VerificationKey::from(&sk.expose_secret())
+    }
+}
+
 impl<T: SigType> TryFrom<[u8; 32]> for SigningKey<T> {
     type Error = Error;
 
@@ -44,9 +118,50 @@ impl<T: SigType> TryFrom<[u8; 32]> for SigningKey<T> {
     }
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+impl<'a, T: SigType> TryFrom<&'a [u8]> for SigningKey<T> {
+    type Error = Error;
+
+    /// Parse a signing key from a slice, returning
+    /// [`Error::MalformedSigningKey`] if it is not a 32-byte canonical scalar
+    /// encoding.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; 32]>::try_from(bytes)
+            .map_err(|_| Error::MalformedSigningKey)?
+            .try_into()
+    }
+}
+
+/// The serde representation of a [`SigningKey`]: a hex string in
+/// human-readable formats, and the raw 32 bytes otherwise.
 struct SerdeHelper([u8; 32]);
 
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "SerdeHelper")]
+struct BinarySerdeHelper([u8; 32]);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SerdeHelper {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&crate::encoding::Hex(&self.0))
+        } else {
+            BinarySerdeHelper(self.0).serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SerdeHelper {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            crate::encoding::deserialize_hex::<_, 32>(deserializer).map(SerdeHelper)
+        } else {
+            BinarySerdeHelper::deserialize(deserializer).map(|helper| SerdeHelper(helper.0))
+        }
+    }
+}
+
 impl<T: SigType> TryFrom<SerdeHelper> for SigningKey<T> {
     type Error = Error;
 
@@ -64,22 +179,236 @@ impl<T: SigType> From<SigningKey<T>> for SerdeHelper {
 impl SigningKey<SpendAuth> {
     /// Randomize this public key with the given `randomizer`.
     pub fn randomize(&self, randomizer: &Randomizer) -> SigningKey<SpendAuth> {
-        let reddsa_sk = self.0.randomize(randomizer);
+        let reddsa_sk = self.0.randomize(&randomizer.0);
         SigningKey(reddsa_sk)
     }
 }
 
+/// A `SpendAuth` signing key randomized with a [`Randomizer`], which keeps
+/// the original key's verification key and the randomizer alongside it.
+///
+/// This saves callers building many spends from tracking which randomizer
+/// belongs to which key. The `Debug` output only shows the verification key.
+#[derive(Copy, Clone)]
+pub struct RandomizedSigningKey {
+    ak: VerificationKey<SpendAuth>,
+    randomizer: Randomizer,
+    rsk: SigningKey<SpendAuth>,
+}
+
+impl core::fmt::Debug for RandomizedSigningKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RandomizedSigningKey")
+            .field("verification_key", &self.verification_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RandomizedSigningKey {
+    /// Randomize `sk` with `randomizer`.
+    pub fn new(sk: &SigningKey<SpendAuth>, randomizer: Randomizer) -> RandomizedSigningKey {
+        RandomizedSigningKey {
+            ak: sk.into(),
+            randomizer,
+            rsk: sk.randomize(&randomizer),
+        }
+    }
+
+    /// The randomizer applied to the original key.
+    pub fn randomizer(&self) -> Randomizer {
+        self.randomizer
+    }
+
+    /// The randomized signing key.
+    pub fn signing_key(&self) -> SigningKey<SpendAuth> {
+        self.rsk
+    }
+
+    /// The randomized verification key `rk`, which signatures by this key
+    /// verify under.
+    pub fn verification_key(&self) -> VerificationKey<SpendAuth> {
+        (&self.rsk).into()
+    }
+
+    /// The verification key `ak` of the original, unrandomized key.
+    ///
+    /// Together with [`RandomizedSigningKey::randomizer`], this proves that
+    /// the randomized key belongs to the original key; check the proof with
+    /// [`VerificationKey::is_randomization_of`].
+    pub fn original_verification_key(&self) -> VerificationKey<SpendAuth> {
+        self.ak
+    }
+
+    /// Sign `msg` with the randomized key.
+    pub fn sign<R: RngCore + CryptoRng>(&self, rng: R, msg: &[u8]) -> Signature<SpendAuth> {
+        self.rsk.sign(rng, msg)
+    }
+}
+
+impl SigningKey<Binding> {
+    /// Combine partial binding signing keys, such as the value commitment
+    /// trapdoors held by different parties to a transaction, into their sum.
+    ///
+    /// The result is the binding signing key for the sum of the partial
+    /// verification keys (see [`VerificationKey::combine`]). Returns
+    /// [`Error::IdentityKey`] if the sum is zero, including when `keys` is
+    /// empty, since its verification key would be the identity.
+    pub fn combine<'a, I>(keys: I) -> Result<SigningKey<Binding>, Error>
+    where
+        I: IntoIterator<Item = &'a SigningKey<Binding>>,
+    {
+        let sum = keys.into_iter().fold(jubjub::Scalar::zero(), |sum, sk| {
+            sum + jubjub::Scalar::from_bytes(&sk.to_bytes())
+                .expect("signing key encodings are canonical")
+        });
+        if bool::from(sum.ct_eq(&jubjub::Scalar::zero())) {
+            return Err(Error::IdentityKey);
+        }
+        SigningKey::try_from(sum.to_bytes())
+    }
+}
+
 impl<T: SigType> SigningKey<T> {
+    /// Parse a signing key from its canonical 32-byte encoding.
+    ///
+    /// Returns [`Error::MalformedSigningKey`] if the bytes are not a canonical
+    /// scalar encoding.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<SigningKey<T>, Error> {
+        SigningKey::try_from(*bytes)
+    }
+
+    /// The canonical 32-byte encoding of this signing key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        (*self).into()
+    }
+
     /// Generate a new signing key.
     pub fn new<R: RngCore + CryptoRng>(rng: R) -> SigningKey<T> {
         let reddsa_sk = reddsa::SigningKey::new(rng);
         SigningKey(reddsa_sk)
     }
 
+    /// Generate a new signing key in a [`SecretSigningKey`].
+    #[cfg(feature = "secrecy")]
+    pub fn new_secret<R: RngCore + CryptoRng>(rng: R) -> SecretSigningKey<T> {
+        SecretSigningKey::from(SigningKey::new(rng))
+    }
+
+    /// Generate a new signing key, checking the RNG output for obvious
+    /// failures first.
+    ///
+    /// The key is the same as [`SigningKey::new`] would produce from the same
+    /// RNG output, but [`Error::WeakRandomness`] is returned instead if the
+    /// output is constant, repeats itself, or has implausibly few distinct
+    /// byte values. These checks are a defense-in-depth heuristic, not a
+    /// guarantee of entropy. Tests using a fixed or counting RNG should use
+    /// [`SigningKey::new`], which performs no checks.
+    pub fn new_checked<R: RngCore + CryptoRng>(rng: R) -> Result<SigningKey<T>, Error> {
+        let mut rng = CheckedRng::new(rng);
+        let sk = SigningKey::new(&mut rng);
+        rng.finish().map(|()| sk)
+    }
+
+    /// Derive a signing key from a secret `seed` and a public `info` string,
+    /// such as an account or index path, for deterministic wallets.
+    ///
+    /// The key is the scalar
+    ///
+    /// ```text
+    /// BLAKE2b-512("RedJubjubKeyDerv", len(seed) || seed || info) mod r
+    /// ```
+    ///
+    /// where the personalization is the 16 bytes `RedJubjubKeyDerv`,
+    /// `len(seed)` is the seed length as a little-endian `u64`, and `r` is
+    /// the JubJub scalar field order. Different `info` strings give
+    /// independent keys from the same seed.
+    ///
+    /// `seed` must be secret and have at least 32 bytes of entropy; this
+    /// function does not stretch weak seeds such as passwords.
+    pub fn from_seed(seed: &[u8], info: &[u8]) -> SigningKey<T> {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(b"RedJubjubKeyDerv")
+            .to_state()
+            .update(&(seed.len() as u64).to_le_bytes())
+            .update(seed)
+            .update(info)
+            .finalize();
+        let sk_bytes = jubjub::Scalar::from_bytes_wide(hash.as_array()).to_bytes();
+        SigningKey::try_from(sk_bytes).expect("reduced scalars are canonical")
+    }
+
     /// Create a signature of type `T` on `msg` using this `SigningKey`.
     // Similar to signature::Signer but without boxed errors.
     pub fn sign<R: RngCore + CryptoRng>(&self, rng: R, msg: &[u8]) -> Signature<T> {
         let reddsa_sig = self.0.sign(rng, msg);
         Signature(reddsa_sig)
     }
+
+    /// Create a signature of type `T` on `msg` using this `SigningKey`,
+    /// checking the RNG output used for the nonce as
+    /// [`SigningKey::new_checked`] does.
+    ///
+    /// Returns [`Error::WeakRandomness`], and discards the signature, if the
+    /// RNG output fails a check.
+    pub fn sign_checked<R: RngCore + CryptoRng>(
+        &self,
+        rng: R,
+        msg: &[u8],
+    ) -> Result<Signature<T>, Error> {
+        let mut rng = CheckedRng::new(rng);
+        let sig = self.sign(&mut rng, msg);
+        rng.finish().map(|()| sig)
+    }
+
+    /// Create a signature of type `T` on `msg` using this `SigningKey`,
+    /// without requiring an RNG.
+    ///
+    /// In the style of [RFC 6979], the 80 random bytes that [`SigningKey::sign`]
+    /// would draw from its RNG are instead derived from the key and message.
+    /// With `B(i) = BLAKE2b-512(sk || msg || LE64(i))`, personalized with
+    /// `"RedJubjubDetSign"`, where `sk` is the 32-byte encoding of this key
+    /// and `LE64(i)` the 8-byte little-endian counter, the bytes are
+    /// `B(0) || B(1)[..16]`. The nonce is then `H*(bytes || vk || msg)` as in
+    /// a randomized signature, so signing the same message with the same key
+    /// always produces the same signature. This is useful for known-answer
+    /// tests and for devices without an entropy source.
+    ///
+    /// [RFC 6979]: https://www.rfc-editor.org/rfc/rfc6979
+    pub fn sign_deterministic(&self, msg: &[u8]) -> Signature<T> {
+        let sk_bytes: [u8; 32] = self.0.into();
+        let rng = HashRng::new(b"RedJubjubDetSign")
+            .update(&sk_bytes[..])
+            .update(msg);
+        self.sign(rng, msg)
+    }
+}
+
+#[cfg(feature = "signature-traits")]
+impl<T: SigType> ::signature::Signer<Signature<T>> for SigningKey<T> {
+    /// Signs `msg` with [`SigningKey::sign_deterministic`], since this trait
+    /// does not provide an RNG.
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<T>, ::signature::Error> {
+        Ok(self.sign_deterministic(msg))
+    }
+}
+
+#[cfg(feature = "signature-traits")]
+impl<T: SigType> ::signature::RandomizedSigner<Signature<T>> for SigningKey<T> {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl ::signature::rand_core::CryptoRngCore,
+        msg: &[u8],
+    ) -> Result<Signature<T>, ::signature::Error> {
+        Ok(self.sign(rng, msg))
+    }
+}
+
+#[cfg(feature = "signature-traits")]
+impl<T: SigType> ::signature::Keypair for SigningKey<T> {
+    type VerifyingKey = VerificationKey<T>;
+
+    fn verifying_key(&self) -> Self::VerifyingKey {
+        VerificationKey::from(self)
+    }
 }
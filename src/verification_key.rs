@@ -8,9 +8,20 @@
 // - Deirdre Connolly <deirdre@zfnd.org>
 // - Henry de Valence <hdevalence@hdevalence.ca>
 
-use core::{convert::TryFrom, hash::Hash};
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
-use crate::{Error, Randomizer, SigType, Signature, SpendAuth};
+use alloc::vec::Vec;
+
+use crate::{
+    encoding::Hex, scalar_mul::FixedBaseTable, Binding, Error, Randomizer, SigType, Signature,
+    SpendAuth,
+};
 
 /// A refinement type for `[u8; 32]` indicating that the bytes represent
 /// an encoding of a RedJubJub verification key.
@@ -18,24 +29,144 @@ use crate::{Error, Randomizer, SigType, Signature, SpendAuth};
 /// This is useful for representing a compressed verification key; the
 /// [`VerificationKey`] type in this library holds other decompressed state
 /// used in signature verification.
-#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///
+/// With a human-readable serde format (such as JSON) the bytes are encoded as
+/// a hex string; binary formats use the compact 32-byte encoding.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct VerificationKeyBytes<T: SigType>(
     pub(crate) reddsa::VerificationKeyBytes<T::RedDSASigType>,
 );
 
+impl<T: SigType> Hash for VerificationKeyBytes<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state)
+    }
+}
+
+/// Ordered by the byte encoding, for use as `BTreeMap` keys.
+impl<T: SigType> PartialOrd for VerificationKeyBytes<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: SigType> Ord for VerificationKeyBytes<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(&other.to_bytes())
+    }
+}
+
+impl<T: SigType> fmt::Display for VerificationKeyBytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes: [u8; 32] = self.0.into();
+        Hex(&bytes).fmt(f)
+    }
+}
+
+/// Parses the hex encoding produced by `Display`: exactly 64 hex digits, of
+/// either case. The encoding itself is not checked.
+impl<T: SigType> FromStr for VerificationKeyBytes<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::encoding::decode_hex_32(s)
+            .map(VerificationKeyBytes::from)
+            .ok_or(Error::MalformedVerificationKey)
+    }
+}
+
+/// The binary serde representation of [`VerificationKeyBytes`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "VerificationKeyBytes")]
+struct SerdeHelper<T: SigType>(reddsa::VerificationKeyBytes<T::RedDSASigType>);
+
+#[cfg(feature = "serde")]
+impl<T: SigType> serde::Serialize for VerificationKeyBytes<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            SerdeHelper::<T>(self.0).serialize(serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: SigType> serde::Deserialize<'de> for VerificationKeyBytes<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            crate::encoding::deserialize_hex::<_, 32>(deserializer).map(VerificationKeyBytes::from)
+        } else {
+            SerdeHelper::<T>::deserialize(deserializer).map(|helper| VerificationKeyBytes(helper.0))
+        }
+    }
+}
+
 impl<T: SigType> From<[u8; 32]> for VerificationKeyBytes<T> {
     fn from(bytes: [u8; 32]) -> VerificationKeyBytes<T> {
         VerificationKeyBytes(reddsa::VerificationKeyBytes::from(bytes))
     }
 }
 
+impl<'a, T: SigType> TryFrom<&'a [u8]> for VerificationKeyBytes<T> {
+    type Error = Error;
+
+    /// Wrap a slice, returning [`Error::MalformedVerificationKey`] if it is
+    /// not exactly 32 bytes long. The encoding itself is not checked.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; 32]>::try_from(bytes)
+            .map(VerificationKeyBytes::from)
+            .map_err(|_| Error::MalformedVerificationKey)
+    }
+}
+
 impl<T: SigType> From<VerificationKeyBytes<T>> for [u8; 32] {
     fn from(refined: VerificationKeyBytes<T>) -> [u8; 32] {
         refined.0.into()
     }
 }
 
+impl<T: SigType> VerificationKeyBytes<T> {
+    /// Wrap a 32-byte verification key encoding, without checking it.
+    pub fn from_bytes(bytes: &[u8; 32]) -> VerificationKeyBytes<T> {
+        VerificationKeyBytes::from(*bytes)
+    }
+
+    /// The wrapped 32-byte encoding.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        (*self).into()
+    }
+
+    /// Decompress the point encoded by these bytes.
+    pub(crate) fn to_point(self) -> Result<jubjub::AffinePoint, Error> {
+        let bytes: [u8; 32] = self.into();
+        Option::from(jubjub::AffinePoint::from_bytes(bytes)).ok_or(Error::MalformedVerificationKey)
+    }
+
+    /// Returns whether these bytes encode a point of small order (including
+    /// the identity).
+    ///
+    /// Small-order verification keys are accepted by [`VerificationKey`]
+    /// parsing, as the RedDSA specification allows them, but Zcash Sapling
+    /// rejects them for the spend authorization key `rk`. The check itself
+    /// is constant-time.
+    ///
+    /// Returns [`Error::MalformedVerificationKey`] if the bytes are not a
+    /// canonical point encoding.
+    pub fn is_small_order(&self) -> Result<bool, Error> {
+        Ok(self.to_point()?.is_small_order().into())
+    }
+
+    /// Returns whether these bytes encode the identity point.
+    ///
+    /// Returns [`Error::MalformedVerificationKey`] if the bytes are not a
+    /// canonical point encoding.
+    pub fn is_identity(&self) -> Result<bool, Error> {
+        Ok(self.to_point()?.is_identity().into())
+    }
+}
+
 /// A valid RedJubJub verification key.
 ///
 /// This type holds decompressed state used in signature verification; if the
@@ -44,11 +175,12 @@ impl<T: SigType> From<VerificationKeyBytes<T>> for [u8; 32] {
 ///
 /// ## Consensus properties
 ///
-/// The `TryFrom<VerificationKeyBytes>` conversion performs the following Zcash
-/// consensus rule checks:
+/// The `TryFrom<VerificationKeyBytes>` conversion checks that the bytes are a
+/// canonical encoding of a verification key.
 ///
-/// 1. The check that the bytes are a canonical encoding of a verification key;
-/// 2. The check that the verification key is not a point of small order.
+/// It does not reject points of small order, since the RedDSA specification
+/// allows them. Zcash Sapling rejects small-order spend authorization keys
+/// `rk`; check those separately with [`VerificationKeyBytes::is_small_order`].
 #[derive(PartialEq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "VerificationKeyBytes<T>"))]
@@ -56,6 +188,45 @@ impl<T: SigType> From<VerificationKeyBytes<T>> for [u8; 32] {
 #[cfg_attr(feature = "serde", serde(bound = "T: SigType"))]
 pub struct VerificationKey<T: SigType>(pub(crate) reddsa::VerificationKey<T::RedDSASigType>);
 
+impl<T: SigType> Eq for VerificationKey<T> {}
+
+/// Hashed by the byte encoding, consistently with [`VerificationKeyBytes`].
+impl<T: SigType> Hash for VerificationKey<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        VerificationKeyBytes::from(*self).hash(state)
+    }
+}
+
+/// Ordered by the byte encoding, for use as `BTreeMap` keys.
+impl<T: SigType> PartialOrd for VerificationKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: SigType> Ord for VerificationKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(&other.to_bytes())
+    }
+}
+
+impl<T: SigType> fmt::Display for VerificationKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        VerificationKeyBytes::<T>::from(*self).fmt(f)
+    }
+}
+
+/// Parses the hex encoding produced by `Display`, returning
+/// [`Error::MalformedVerificationKey`] unless it is 64 hex digits encoding a
+/// valid key.
+impl<T: SigType> FromStr for VerificationKey<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VerificationKey::try_from(s.parse::<VerificationKeyBytes<T>>()?)
+    }
+}
+
 impl<T: SigType> From<VerificationKey<T>> for VerificationKeyBytes<T> {
     fn from(pk: VerificationKey<T>) -> VerificationKeyBytes<T> {
         VerificationKeyBytes(pk.0.into())
@@ -86,19 +257,230 @@ impl<T: SigType> TryFrom<[u8; 32]> for VerificationKey<T> {
     }
 }
 
+impl<'a, T: SigType> TryFrom<&'a [u8]> for VerificationKey<T> {
+    type Error = Error;
+
+    /// Parse a verification key from a slice, returning
+    /// [`Error::MalformedVerificationKey`] if it is not a 32-byte canonical
+    /// point encoding.
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        VerificationKeyBytes::try_from(bytes)?.try_into()
+    }
+}
+
 impl VerificationKey<SpendAuth> {
     /// Randomize this verification key with the given `randomizer`.
     ///
     /// Randomization is only supported for `SpendAuth` keys.
     pub fn randomize(&self, randomizer: &Randomizer) -> VerificationKey<SpendAuth> {
-        VerificationKey(self.0.randomize(randomizer))
+        VerificationKey(self.0.randomize(&randomizer.0))
+    }
+
+    /// Returns whether this key is the randomization of `ak` with
+    /// `randomizer`, so that audit tooling can link a spend's `rk` to the
+    /// wallet key it came from.
+    pub fn is_randomization_of(
+        &self,
+        ak: &VerificationKey<SpendAuth>,
+        randomizer: &Randomizer,
+    ) -> bool {
+        ak.randomize(randomizer) == *self
+    }
+
+    /// Randomize this verification key with each of the given `randomizers`.
+    ///
+    /// The result is the same as calling [`VerificationKey::randomize`] for
+    /// each randomizer, but for more than a few randomizers it is faster: the
+    /// multiples of the basepoint are computed with a shared precomputed
+    /// table, and the results are converted to affine form with a single
+    /// field inversion.
+    pub fn randomize_many(&self, randomizers: &[Randomizer]) -> Vec<VerificationKey<SpendAuth>> {
+        // Below this, building the table costs more than it saves.
+        const TABLE_THRESHOLD: usize = 8;
+        if randomizers.len() < TABLE_THRESHOLD {
+            return randomizers.iter().map(|r| self.randomize(r)).collect();
+        }
+
+        let ak = jubjub::ExtendedPoint::from(
+            VerificationKeyBytes::from(*self)
+                .to_point()
+                .expect("verification keys are valid points"),
+        );
+        let basepoint =
+            VerificationKeyBytes::<SpendAuth>::from(crate::params::SPENDAUTHSIG_BASEPOINT_BYTES)
+                .to_point()
+                .expect("the basepoint encoding is valid");
+        let table = FixedBaseTable::new(basepoint.into());
+
+        let mut rks: Vec<_> = randomizers.iter().map(|r| ak + table.mul(&r.0)).collect();
+        jubjub::batch_normalize(&mut rks)
+            .map(|rk| {
+                VerificationKey::try_from(rk.to_bytes()).expect("randomized keys are valid points")
+            })
+            .collect()
+    }
+}
+
+impl VerificationKey<Binding> {
+    /// Combine partial binding verification keys into their sum.
+    ///
+    /// This is the verification key of [`SigningKey::combine`] applied to the
+    /// corresponding signing keys, so each party can contribute its share of
+    /// the binding validating key without revealing its trapdoors. Returns
+    /// [`Error::IdentityKey`] if the sum is the identity, including when
+    /// `keys` is empty.
+    ///
+    /// [`SigningKey::combine`]: crate::SigningKey::combine
+    pub fn combine<'a, I>(keys: I) -> Result<VerificationKey<Binding>, Error>
+    where
+        I: IntoIterator<Item = &'a VerificationKey<Binding>>,
+    {
+        let sum = keys
+            .into_iter()
+            .fold(jubjub::ExtendedPoint::identity(), |sum, vk| {
+                sum + VerificationKeyBytes::from(*vk)
+                    .to_point()
+                    .expect("verification keys are valid points")
+            });
+        if bool::from(sum.is_identity()) {
+            return Err(Error::IdentityKey);
+        }
+        VerificationKey::try_from(jubjub::AffinePoint::from(sum).to_bytes())
     }
 }
 
 impl<T: SigType> VerificationKey<T> {
+    /// Parse a verification key from its 32-byte encoding.
+    ///
+    /// Returns [`Error::MalformedVerificationKey`] if the bytes are not a
+    /// canonical point encoding.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<VerificationKey<T>, Error> {
+        VerificationKey::try_from(*bytes)
+    }
+
+    /// The 32-byte encoding of this verification key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        (*self).into()
+    }
+
     /// Verify a purported `signature` over `msg` made by this verification key.
     // This is similar to impl signature::Verifier but without boxed errors
     pub fn verify(&self, msg: &[u8], signature: &Signature<T>) -> Result<(), Error> {
         self.0.verify(msg, &signature.0).map_err(|e| e.into())
     }
 }
+
+/// Verify the signature encoded by `sig_bytes` over `msg` by the verification
+/// key encoded by `vk_bytes`, for callers that only hold raw bytes.
+///
+/// This is shorthand for parsing a [`VerificationKey`] and calling
+/// [`VerificationKey::verify`], with the same consensus checks: it returns
+/// [`Error::MalformedVerificationKey`] if `vk_bytes` is not a canonical point
+/// encoding, and [`Error::InvalidSignature`] if the signature is
+/// non-canonical or does not verify. [`batch::Item::from_raw`] is the batch
+/// counterpart.
+///
+/// [`batch::Item::from_raw`]: crate::batch::Item::from_raw
+pub fn verify<T: SigType>(
+    vk_bytes: [u8; 32],
+    msg: &[u8],
+    sig_bytes: [u8; 64],
+) -> Result<(), Error> {
+    VerificationKey::<T>::try_from(vk_bytes)?.verify(msg, &Signature::from(sig_bytes))
+}
+
+/// A verification key with precomputed tables of multiples of the key and of
+/// the basepoint, for verifying many signatures by the same key with lower
+/// latency.
+///
+/// [`PreparedVerificationKey::verify`] accepts exactly the signatures that
+/// [`VerificationKey::verify`] does, but each verification takes about a
+/// fifth of the time. Preparing a key costs about as much as four
+/// verifications.
+///
+/// The two tables each hold 64 × 16 affine Niels points of 96 bytes, so a
+/// prepared key owns 192 KiB of heap memory, against 32 bytes for a
+/// [`VerificationKey`]. Prepare only the keys that sign often, and drop the
+/// tables once they are no longer needed.
+pub struct PreparedVerificationKey<T: SigType> {
+    vk: VerificationKey<T>,
+    vk_table: FixedBaseTable,
+    basepoint_table: FixedBaseTable,
+}
+
+impl<T: SigType> fmt::Debug for PreparedVerificationKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PreparedVerificationKey")
+            .field(&self.vk)
+            .finish()
+    }
+}
+
+impl<T: SigType> From<VerificationKey<T>> for PreparedVerificationKey<T> {
+    fn from(vk: VerificationKey<T>) -> Self {
+        vk.precompute()
+    }
+}
+
+impl<T: SigType> VerificationKey<T> {
+    /// Precompute tables for verifying many signatures by this key; see
+    /// [`PreparedVerificationKey`].
+    pub fn precompute(&self) -> PreparedVerificationKey<T> {
+        let point = |bytes: [u8; 32]| {
+            VerificationKeyBytes::<T>::from(bytes)
+                .to_point()
+                .expect("verification keys and basepoints are valid points")
+                .into()
+        };
+        PreparedVerificationKey {
+            vk: *self,
+            vk_table: FixedBaseTable::new(point(self.to_bytes())),
+            basepoint_table: FixedBaseTable::new(point(
+                <T as crate::private::Sealed>::BASEPOINT_BYTES,
+            )),
+        }
+    }
+}
+
+impl<T: SigType> PreparedVerificationKey<T> {
+    /// The verification key these tables were computed for.
+    pub fn verification_key(&self) -> VerificationKey<T> {
+        self.vk
+    }
+
+    /// Verify a purported `signature` over `msg` made by this verification key.
+    #[allow(non_snake_case)]
+    pub fn verify(&self, msg: &[u8], signature: &Signature<T>) -> Result<(), Error> {
+        let r_bytes = signature.r_bytes();
+        let R = Option::<jubjub::AffinePoint>::from(jubjub::AffinePoint::from_bytes(r_bytes))
+            .ok_or(Error::InvalidSignature)?;
+        let s = Option::<jubjub::Scalar>::from(jubjub::Scalar::from_bytes(&signature.s_bytes()))
+            .ok_or(Error::InvalidSignature)?;
+
+        let hash = blake2b_simd::Params::new()
+            .hash_length(64)
+            .personal(crate::params::H_STAR_PERSONALIZATION)
+            .to_state()
+            .update(&r_bytes)
+            .update(&self.vk.to_bytes())
+            .update(msg)
+            .finalize();
+        let c = jubjub::Scalar::from_bytes_wide(hash.as_array());
+
+        // As in `VerificationKey::verify`, check the cofactored equation
+        // `[8]([s]B - [c]A - R) = 0`.
+        let check = self.basepoint_table.mul_vartime(&s) - self.vk_table.mul_vartime(&c) - R;
+        if bool::from(check.is_small_order()) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(feature = "signature-traits")]
+impl<T: SigType> ::signature::Verifier<Signature<T>> for VerificationKey<T> {
+    fn verify(&self, msg: &[u8], signature: &Signature<T>) -> Result<(), ::signature::Error> {
+        VerificationKey::verify(self, msg, signature).map_err(|_| ::signature::Error::new())
+    }
+}
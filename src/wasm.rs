@@ -0,0 +1,230 @@
+// -*- mode: rust; -*-
+//
+// This file is part of redjubjub.
+// Copyright (c) 2019-2021 Zcash Foundation
+// See LICENSE for licensing information.
+
+//! JavaScript bindings, generated with [`wasm-bindgen`].
+//!
+//! The signature type parameter cannot cross into JavaScript, so the
+//! exported classes carry it at runtime instead, selected with the exported
+//! `SigType` enum. Keys of one type cannot be used with signatures or keys of
+//! the other: verification returns `false`, as it would for any other
+//! invalid signature.
+//!
+//! Randomness comes from `crypto.getRandomValues` on
+//! `wasm32-unknown-unknown`, through `getrandom`'s `js` backend. Build with
+//!
+//! ```shell,no_run
+//! wasm-pack build --target web -- --features wasm
+//! ```
+//!
+//! Recent `wasm-bindgen` releases need a newer compiler than the crate's
+//! MSRV.
+//!
+//! [`wasm-bindgen`]: https://rustwasm.github.io/docs/wasm-bindgen/
+
+use std::{convert::TryFrom, string::ToString, vec::Vec};
+
+use rand_core::OsRng;
+use wasm_bindgen::prelude::*;
+
+use crate::{batch, Binding, Error, Signature, SpendAuth};
+
+/// Selects the RedJubjub parameters, as the type parameter does in Rust.
+#[wasm_bindgen(js_name = SigType)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum JsSigType {
+    /// Zcash's `BindingSig`.
+    Binding = 0,
+    /// Zcash's `SpendAuthSig`.
+    SpendAuth = 1,
+}
+
+#[derive(Copy, Clone)]
+enum AnySigningKey {
+    Binding(crate::SigningKey<Binding>),
+    SpendAuth(crate::SigningKey<SpendAuth>),
+}
+
+#[derive(Copy, Clone)]
+enum AnyVerificationKey {
+    Binding(crate::VerificationKey<Binding>),
+    SpendAuth(crate::VerificationKey<SpendAuth>),
+}
+
+fn js_error(e: Error) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn array<const N: usize>(bytes: &[u8]) -> Result<[u8; N], JsError> {
+    <[u8; N]>::try_from(bytes).map_err(|_| JsError::new(&std::format!("expected {} bytes", N)))
+}
+
+/// A RedJubjub signing key.
+#[wasm_bindgen(js_name = SigningKey)]
+pub struct JsSigningKey(AnySigningKey);
+
+#[wasm_bindgen(js_class = SigningKey)]
+impl JsSigningKey {
+    /// Generate a new signing key.
+    pub fn generate(sig_type: JsSigType) -> JsSigningKey {
+        JsSigningKey(match sig_type {
+            JsSigType::Binding => AnySigningKey::Binding(crate::SigningKey::new(OsRng)),
+            JsSigType::SpendAuth => AnySigningKey::SpendAuth(crate::SigningKey::new(OsRng)),
+        })
+    }
+
+    /// Parse a signing key from its 32-byte encoding.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(sig_type: JsSigType, bytes: &[u8]) -> Result<JsSigningKey, JsError> {
+        let bytes = array::<32>(bytes)?;
+        Ok(JsSigningKey(match sig_type {
+            JsSigType::Binding => {
+                AnySigningKey::Binding(crate::SigningKey::try_from(bytes).map_err(js_error)?)
+            }
+            JsSigType::SpendAuth => {
+                AnySigningKey::SpendAuth(crate::SigningKey::try_from(bytes).map_err(js_error)?)
+            }
+        }))
+    }
+
+    /// The 32-byte encoding of this signing key.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.0 {
+            AnySigningKey::Binding(sk) => sk.to_bytes().to_vec(),
+            AnySigningKey::SpendAuth(sk) => sk.to_bytes().to_vec(),
+        }
+    }
+
+    /// The signature type of this key.
+    #[wasm_bindgen(getter, js_name = sigType)]
+    pub fn sig_type(&self) -> JsSigType {
+        match self.0 {
+            AnySigningKey::Binding(_) => JsSigType::Binding,
+            AnySigningKey::SpendAuth(_) => JsSigType::SpendAuth,
+        }
+    }
+
+    /// The verification key for this signing key.
+    #[wasm_bindgen(js_name = verificationKey)]
+    pub fn verification_key(&self) -> JsVerificationKey {
+        JsVerificationKey(match &self.0 {
+            AnySigningKey::Binding(sk) => AnyVerificationKey::Binding(sk.into()),
+            AnySigningKey::SpendAuth(sk) => AnyVerificationKey::SpendAuth(sk.into()),
+        })
+    }
+
+    /// Sign `msg`.
+    pub fn sign(&self, msg: &[u8]) -> JsSignature {
+        JsSignature(match self.0 {
+            AnySigningKey::Binding(sk) => sk.sign(OsRng, msg).into(),
+            AnySigningKey::SpendAuth(sk) => sk.sign(OsRng, msg).into(),
+        })
+    }
+}
+
+/// A RedJubjub verification key.
+#[wasm_bindgen(js_name = VerificationKey)]
+pub struct JsVerificationKey(AnyVerificationKey);
+
+#[wasm_bindgen(js_class = VerificationKey)]
+impl JsVerificationKey {
+    /// Parse a verification key from its 32-byte encoding.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(sig_type: JsSigType, bytes: &[u8]) -> Result<JsVerificationKey, JsError> {
+        let bytes = array::<32>(bytes)?;
+        Ok(JsVerificationKey(match sig_type {
+            JsSigType::Binding => AnyVerificationKey::Binding(
+                crate::VerificationKey::try_from(bytes).map_err(js_error)?,
+            ),
+            JsSigType::SpendAuth => AnyVerificationKey::SpendAuth(
+                crate::VerificationKey::try_from(bytes).map_err(js_error)?,
+            ),
+        }))
+    }
+
+    /// The 32-byte encoding of this verification key.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.0 {
+            AnyVerificationKey::Binding(vk) => vk.to_bytes().to_vec(),
+            AnyVerificationKey::SpendAuth(vk) => vk.to_bytes().to_vec(),
+        }
+    }
+
+    /// The signature type of this key.
+    #[wasm_bindgen(getter, js_name = sigType)]
+    pub fn sig_type(&self) -> JsSigType {
+        match self.0 {
+            AnyVerificationKey::Binding(_) => JsSigType::Binding,
+            AnyVerificationKey::SpendAuth(_) => JsSigType::SpendAuth,
+        }
+    }
+
+    /// Check whether `sig` is a valid signature of `msg` by this key.
+    pub fn verify(&self, msg: &[u8], sig: &JsSignature) -> bool {
+        match &self.0 {
+            AnyVerificationKey::Binding(vk) => vk.verify(msg, &Signature::from(sig.0)),
+            AnyVerificationKey::SpendAuth(vk) => vk.verify(msg, &Signature::from(sig.0)),
+        }
+        .is_ok()
+    }
+}
+
+/// A RedJubjub signature.
+#[wasm_bindgen(js_name = Signature)]
+pub struct JsSignature([u8; 64]);
+
+#[wasm_bindgen(js_class = Signature)]
+impl JsSignature {
+    /// Parse a signature from its 64-byte encoding.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsSignature, JsError> {
+        Ok(JsSignature(array::<64>(bytes)?))
+    }
+
+    /// The 64-byte encoding of this signature.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+/// A batch verification context, accepting signatures of both types.
+#[wasm_bindgen(js_name = BatchVerifier)]
+pub struct JsBatchVerifier(batch::Verifier);
+
+#[wasm_bindgen(js_class = BatchVerifier)]
+impl JsBatchVerifier {
+    /// Construct a new, empty batch verifier.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> JsBatchVerifier {
+        JsBatchVerifier(batch::Verifier::new())
+    }
+
+    /// Queue the signature `sig` of `msg` by `vk` for verification.
+    pub fn queue(&mut self, vk: &JsVerificationKey, msg: &[u8], sig: &JsSignature) {
+        match vk.0 {
+            AnyVerificationKey::Binding(vk) => self.0.queue((
+                crate::VerificationKeyBytes::from(vk),
+                Signature::<Binding>::from(sig.0),
+                &msg,
+            )),
+            AnyVerificationKey::SpendAuth(vk) => self.0.queue((
+                crate::VerificationKeyBytes::from(vk),
+                Signature::<SpendAuth>::from(sig.0),
+                &msg,
+            )),
+        }
+    }
+
+    /// Verify all queued signatures, returning `true` only if all are valid.
+    ///
+    /// This consumes the verifier.
+    pub fn verify(self) -> bool {
+        self.0.verify(OsRng).is_ok()
+    }
+}
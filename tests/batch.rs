@@ -30,6 +30,35 @@ fn binding_batch_verify() {
     assert!(batch.verify(rng).is_ok());
 }
 
+#[test]
+fn collected_batch_verify() {
+    let mut rng = thread_rng();
+    let msg = b"BatchVerifyTest";
+    let signed: Vec<_> = (0..8)
+        .map(|_| {
+            let sk = SigningKey::<SpendAuth>::new(thread_rng());
+            let sig = sk.sign(thread_rng(), &msg[..]);
+            (VerificationKeyBytes::from(VerificationKey::from(&sk)), sig)
+        })
+        .collect();
+
+    let batch: batch::Verifier = signed.iter().map(|&(vk, sig)| (vk, sig, msg)).collect();
+    assert_eq!(batch.len(), 8);
+    assert!(batch.verify(&mut rng).is_ok());
+
+    let sk = SigningKey::<Binding>::new(thread_rng());
+    let binding_item = batch::Item::from((
+        VerificationKeyBytes::from(VerificationKey::from(&sk)),
+        sk.sign(thread_rng(), &msg[..]),
+        msg,
+    ));
+    let mut batch = batch::Verifier::new();
+    batch.queue_many(signed.iter().map(|&(vk, sig)| (vk, sig, msg)));
+    batch.extend(std::iter::once(binding_item));
+    assert_eq!(batch.len(), 9);
+    assert!(batch.verify(&mut rng).is_ok());
+}
+
 #[test]
 fn alternating_batch_verify() {
     let mut rng = thread_rng();
@@ -97,3 +126,291 @@ fn bad_batch_verify() {
         }
     }
 }
+
+/// A stand-in for a node's own spend description type.
+struct Spend {
+    rk: VerificationKeyBytes<SpendAuth>,
+    spend_auth_sig: Signature<SpendAuth>,
+    sighash: [u8; 32],
+}
+
+impl batch::BatchItemSource for Spend {
+    type SigType = SpendAuth;
+
+    fn vk_bytes(&self) -> VerificationKeyBytes<SpendAuth> {
+        self.rk
+    }
+
+    fn signature(&self) -> Signature<SpendAuth> {
+        self.spend_auth_sig
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.sighash
+    }
+}
+
+#[test]
+fn batch_item_source_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    let mut spends = Vec::new();
+    for i in 0..8 {
+        let sk = SigningKey::<SpendAuth>::new(&mut rng);
+        let sighash = [i; 32];
+        spends.push(Spend {
+            rk: VerificationKey::from(&sk).into(),
+            spend_auth_sig: sk.sign(&mut rng, &sighash),
+            sighash,
+        });
+    }
+    for spend in &spends {
+        batch.queue_from(spend);
+        assert!(batch::Item::from(spend).verify_single().is_ok());
+    }
+    assert!(batch.verify(&mut rng).is_ok());
+
+    // Tampering with the payload must be detected.
+    let mut batch = batch::Verifier::new();
+    spends[3].sighash[0] ^= 1;
+    for spend in &spends {
+        batch.queue_from(spend);
+    }
+    assert!(batch.verify(rng).is_err());
+}
+
+#[test]
+fn randomized_batch_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    let sk = SigningKey::<SpendAuth>::new(&mut rng);
+    let ak = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    for i in 0..8 {
+        let randomizer = Randomizer::new(&mut rng);
+        let msg = [i; 32];
+        let sig = sk.randomize(&randomizer).sign(&mut rng, &msg);
+        let item = batch::Item::from_randomized(ak, &randomizer, sig, &msg).unwrap();
+        assert!(item.clone().verify_single().is_ok());
+        batch.queue(item);
+
+        // The unrandomized key and a different randomizer must both fail.
+        assert!(batch::Item::from((ak, sig, &msg)).verify_single().is_err());
+        let other = Randomizer::new(&mut rng);
+        assert!(batch::Item::from_randomized(ak, &other, sig, &msg)
+            .unwrap()
+            .verify_single()
+            .is_err());
+    }
+    assert!(batch.verify(rng).is_ok());
+
+    assert_eq!(
+        batch::Item::from_randomized(
+            VerificationKeyBytes::from([0xff; 32]),
+            &Randomizer::new(thread_rng()),
+            Signature::from([0; 64]),
+            b"",
+        )
+        .err(),
+        Some(Error::MalformedVerificationKey)
+    );
+}
+
+#[test]
+fn reused_batch_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::with_capacity(16);
+    assert!(batch.capacity() >= 16);
+    assert!(batch.is_empty());
+
+    for round in 0..3u8 {
+        for i in 0..16u8 {
+            let sk = SigningKey::<Binding>::new(&mut rng);
+            let msg = [round, i];
+            let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+            let sig = if round == 1 && i == 5 {
+                sk.sign(&mut rng, b"wrong")
+            } else {
+                sk.sign(&mut rng, &msg)
+            };
+            batch.queue((vk_bytes, sig, &msg));
+        }
+        assert_eq!(batch.len(), 16);
+        let capacity = batch.capacity();
+        assert_eq!(batch.verify_and_reset(&mut rng).is_ok(), round != 1);
+        assert!(batch.is_empty());
+        assert_eq!(batch.capacity(), capacity);
+    }
+
+    let sk = SigningKey::<Binding>::new(&mut rng);
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    batch.queue((vk_bytes, sk.sign(&mut rng, b"wrong"), b"msg"));
+    batch.reset();
+    assert!(batch.is_empty());
+    assert!(batch.verify(rng).is_ok());
+
+    assert!(batch::Item::size_hint() > 0);
+}
+
+#[test]
+fn verify_each_finds_first_invalid() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    for i in 0..8u8 {
+        let sk = SigningKey::<SpendAuth>::new(&mut rng);
+        let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+        let msg = [i];
+        let sig = if i == 3 || i == 6 {
+            sk.sign(&mut rng, b"wrong")
+        } else {
+            sk.sign(&mut rng, &msg)
+        };
+        batch.queue((vk_bytes, sig, &msg));
+    }
+
+    assert!(batch.clone().verify(&mut rng).is_err());
+    let item_error = batch.verify_each().unwrap_err();
+    assert_eq!(item_error.index, 3);
+    assert_eq!(item_error.error, Error::InvalidSignature);
+    assert_eq!(
+        item_error.to_string(),
+        "Batch item 3 failed: Invalid signature."
+    );
+    #[cfg(feature = "std")]
+    {
+        let source = std::error::Error::source(&item_error).unwrap();
+        assert_eq!(
+            source.downcast_ref::<Error>(),
+            Some(&Error::InvalidSignature)
+        );
+    }
+
+    assert!(batch::Verifier::new().verify_each().is_ok());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_batch_verify() {
+    let mut rng = thread_rng();
+    for n in [0, 1, 100, 300] {
+        let mut batch = batch::Verifier::new();
+        let mut bad_batch = batch::Verifier::new();
+        for i in 0..n {
+            let sk = SigningKey::<SpendAuth>::new(&mut rng);
+            let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+            let msg = (i as u32).to_le_bytes();
+            let sig = sk.sign(&mut rng, &msg);
+            batch.queue((vk_bytes, sig, &msg));
+            let sig = if i == n - 1 {
+                sk.sign(&mut rng, b"wrong")
+            } else {
+                sig
+            };
+            bad_batch.queue((vk_bytes, sig, &msg));
+        }
+        assert!(batch.verify_parallel(&mut rng).is_ok());
+        assert_eq!(bad_batch.verify_parallel(&mut rng).is_ok(), n == 0);
+    }
+}
+
+#[test]
+fn batch_stats_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    let mut bad_batch = batch::Verifier::new();
+    for i in 0..10 {
+        let msg = b"BatchVerifyTest";
+        if i % 3 == 0 {
+            let sk = SigningKey::<Binding>::new(&mut rng);
+            let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+            let sig = sk.sign(&mut rng, &msg[..]);
+            batch.queue((vk_bytes, sig, msg));
+            bad_batch.queue((vk_bytes, sig, msg));
+        } else {
+            let sk = SigningKey::<SpendAuth>::new(&mut rng);
+            let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+            let sig = sk.sign(&mut rng, &msg[..]);
+            batch.queue((vk_bytes, sig, msg));
+            let sig = if i % 4 == 1 {
+                sk.sign(&mut rng, b"bad")
+            } else {
+                sig
+            };
+            bad_batch.queue((vk_bytes, sig, msg));
+        }
+    }
+
+    let (result, stats) = batch.verify_or_recover(&mut rng);
+    assert!(result.is_ok());
+    assert_eq!(stats.items, 10);
+    assert_eq!(stats.binding, 4);
+    assert_eq!(stats.spendauth, 6);
+    assert_eq!(stats.multiscalar_size, 22);
+    assert_eq!(stats.valid, 10);
+    assert_eq!(stats.first_invalid, None);
+
+    // Items 1 and 5 are bad; 9 is a binding signature.
+    let (result, stats) = bad_batch.verify_or_recover(&mut rng);
+    assert_eq!(result, Err(Error::InvalidSignature));
+    assert_eq!(stats.items, 10);
+    assert_eq!(stats.valid, 8);
+    assert_eq!(
+        stats.first_invalid,
+        Some(batch::ItemError {
+            index: 1,
+            error: Error::InvalidSignature
+        })
+    );
+
+    let (result, stats) = batch::Verifier::new().verify_or_recover(&mut rng);
+    assert!(result.is_ok());
+    assert_eq!(
+        (stats.items, stats.valid, stats.multiscalar_size),
+        (0, 0, 2)
+    );
+}
+
+#[test]
+fn key_grouped_batch_verify() {
+    let mut rng = thread_rng();
+    let keys: Vec<_> = (0..3)
+        .map(|_| SigningKey::<SpendAuth>::new(&mut rng))
+        .collect();
+
+    let mut batch = batch::KeyGroupedVerifier::new();
+    let mut bad_batch = batch::KeyGroupedVerifier::new();
+    for i in 0..30u32 {
+        let sk = &keys[i as usize % 3];
+        let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(sk));
+        let msg = i.to_le_bytes();
+        let sig = sk.sign(&mut rng, &msg);
+        batch.queue((vk_bytes, sig, &msg));
+        let sig = if i == 17 {
+            sk.sign(&mut rng, b"bad")
+        } else {
+            sig
+        };
+        bad_batch.queue((vk_bytes, sig, &msg));
+    }
+    assert_eq!(batch.len(), 30);
+    assert_eq!(batch.distinct_keys(), 3);
+    assert_eq!(batch.multiscalar_size(), 34);
+    assert!(batch.verify(&mut rng).is_ok());
+    assert_eq!(bad_batch.verify(&mut rng), Err(Error::InvalidSignature));
+
+    assert!(batch::KeyGroupedVerifier::<Binding>::new()
+        .verify(&mut rng)
+        .is_ok());
+}
+
+#[test]
+fn key_grouped_batch_rejects_malformed_keys() {
+    let mut rng = thread_rng();
+    let sk = SigningKey::<Binding>::new(&mut rng);
+    let msg = b"BatchVerifyTest";
+    let sig = sk.sign(&mut rng, msg);
+
+    let mut batch = batch::KeyGroupedVerifier::new();
+    batch.queue((VerificationKey::from(&sk).into(), sig, msg));
+    batch.queue((VerificationKeyBytes::from([0xff; 32]), sig, msg));
+    assert_eq!(batch.verify(&mut rng), Err(Error::MalformedVerificationKey));
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "serde")]
+
 use std::convert::TryFrom;
 
 use proptest::prelude::*;
@@ -28,9 +30,18 @@ proptest! {
                 // Check 3: From encoding should match original bytes.
                 let bytes_from: [u8; 32] = sk_bincode.into();
                 assert_eq!(&bytes[..], &bytes_from[..]);
+
+                // Check 4: explicit byte conversions should agree.
+                assert_eq!(sk_from.to_bytes(), bytes);
+                assert!(SigningKey::<SpendAuth>::from_bytes(&bytes).is_ok());
             }
             // Both agree on failure
-            (Err(_), Err(_)) => {},
+            (Err(_), Err(_)) => {
+                assert_eq!(
+                    SigningKey::<SpendAuth>::from_bytes(&bytes).err(),
+                    Some(Error::MalformedSigningKey)
+                );
+            },
             _ => panic!("bincode and try_from do not agree"),
         }
     }
@@ -53,6 +64,10 @@ proptest! {
         // Check 3: From encoding should match original bytes.
         let bytes_from: [u8; 32] = pk_bytes_bincode.into();
         assert_eq!(&bytes[..], &bytes_from[..]);
+
+        // Check 4: explicit byte conversions should agree.
+        assert_eq!(VerificationKeyBytes::<SpendAuth>::from_bytes(&bytes), pk_bytes_from);
+        assert_eq!(pk_bytes_from.to_bytes(), bytes);
     }
 
     #[test]
@@ -73,6 +88,9 @@ proptest! {
                 // Check 3: From encoding should match original bytes
                 let bytes_from: [u8; 32] = pk_bincode.into();
                 assert_eq!(&bytes[..], &bytes_from[..]);
+                // Check 4: explicit byte conversions should agree
+                assert_eq!(pk_try_from.to_bytes(), bytes);
+                assert_eq!(VerificationKey::<SpendAuth>::from_bytes(&bytes), Ok(pk_try_from));
             },
             // Both agree on failure
             (Err(_), Err(_)) => {},
@@ -107,5 +125,28 @@ proptest! {
         // Check 3: From encoding should match original bytes.
         let bytes_from: [u8; 64] = sig_bytes_bincode.into();
         assert_eq!(&bytes[..], &bytes_from[..]);
+
+        // Check 4: explicit byte conversions should agree.
+        assert_eq!(Signature::<SpendAuth>::from_bytes(&bytes), sig_bytes_from);
+        assert_eq!(sig_bytes_from.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn randomizer_serialization(
+        bytes in prop::array::uniform32(any::<u8>()),
+    ) {
+        let randomizer_from = Randomizer::from_bytes(&bytes);
+        let randomizer_bincode: Result<Randomizer, _> = bincode::deserialize(&bytes[..]);
+
+        // Both decoding methods should agree, and only accept canonical encodings.
+        match (randomizer_from, randomizer_bincode) {
+            (Ok(from), Ok(bincode)) => {
+                assert_eq!(from, bincode);
+                assert_eq!(&bincode::serialize(&from).unwrap()[..], &bytes[..]);
+                assert_eq!(from.to_bytes(), bytes);
+            }
+            (Err(e), Err(_)) => assert_eq!(e, Error::MalformedRandomizer),
+            _ => panic!("bincode and from_bytes do not agree"),
+        }
     }
 }
@@ -0,0 +1,51 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn keys_and_signatures_in_collections() {
+    let sks: Vec<_> = (0..8)
+        .map(|_| SigningKey::<SpendAuth>::new(thread_rng()))
+        .collect();
+    let vks: Vec<_> = sks.iter().map(VerificationKey::from).collect();
+    let sigs: Vec<_> = sks
+        .iter()
+        .map(|sk| sk.sign(thread_rng(), b"roster"))
+        .collect();
+
+    let roster: BTreeMap<_, _> = vks.iter().copied().zip(0..).collect();
+    assert_eq!(roster.len(), vks.len());
+    for (i, vk) in vks.iter().enumerate() {
+        assert_eq!(roster[vk], i);
+    }
+
+    // The order is the order of the byte encodings.
+    let mut by_bytes: Vec<_> = vks.iter().map(|vk| vk.to_bytes()).collect();
+    by_bytes.sort();
+    let ordered: Vec<_> = roster.keys().map(|vk| vk.to_bytes()).collect();
+    assert_eq!(ordered, by_bytes);
+
+    let mut vk_bytes: Vec<_> = vks
+        .iter()
+        .copied()
+        .map(VerificationKeyBytes::from)
+        .collect();
+    vk_bytes.sort();
+    let ordered: Vec<_> = vk_bytes.iter().map(|vk| vk.to_bytes()).collect();
+    assert_eq!(ordered, by_bytes);
+
+    let set: HashSet<_> = vks.iter().chain(vks.iter()).collect();
+    assert_eq!(set.len(), vks.len());
+    let set: HashSet<_> = vk_bytes.iter().collect();
+    assert_eq!(set.len(), vks.len());
+    let set: HashSet<_> = sigs.iter().chain(sigs.iter()).collect();
+    assert_eq!(set.len(), sigs.len());
+
+    let mut sorted_sigs = sigs.clone();
+    sorted_sigs.sort();
+    assert!(sorted_sigs
+        .windows(2)
+        .all(|pair| pair[0].to_bytes() < pair[1].to_bytes()));
+}
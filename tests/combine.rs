@@ -0,0 +1,44 @@
+use rand::thread_rng;
+
+use redjubjub::{hazmat::Scalar, *};
+
+#[test]
+fn combined_keys_sign_and_verify() {
+    let sks: Vec<_> = (0..3)
+        .map(|_| SigningKey::<Binding>::new(thread_rng()))
+        .collect();
+    let vks: Vec<_> = sks.iter().map(VerificationKey::from).collect();
+
+    let bsk = SigningKey::combine(&sks).unwrap();
+    let bvk = VerificationKey::combine(&vks).unwrap();
+    assert_eq!(VerificationKey::from(&bsk), bvk);
+
+    let msg = b"sighash";
+    let sig = bsk.sign(thread_rng(), msg);
+    assert!(bvk.verify(msg, &sig).is_ok());
+}
+
+#[test]
+fn identity_combinations_are_rejected() {
+    let sk = SigningKey::<Binding>::new(thread_rng());
+    let neg = -Scalar::from_bytes(&sk.to_bytes()).unwrap();
+    let neg_sk = SigningKey::<Binding>::from_bytes(&neg.to_bytes()).unwrap();
+
+    assert_eq!(
+        SigningKey::combine([&sk, &neg_sk]).err(),
+        Some(Error::IdentityKey)
+    );
+    assert_eq!(
+        VerificationKey::combine([&VerificationKey::from(&sk), &VerificationKey::from(&neg_sk)])
+            .err(),
+        Some(Error::IdentityKey)
+    );
+    assert_eq!(
+        SigningKey::combine(std::iter::empty()).err(),
+        Some(Error::IdentityKey)
+    );
+    assert_eq!(
+        VerificationKey::combine(std::iter::empty()).err(),
+        Some(Error::IdentityKey)
+    );
+}
@@ -0,0 +1,371 @@
+//! Differential tests of single and batch verification.
+//!
+//! Any (vk, sig, msg) triple accepted by one of the verification paths and
+//! rejected by another is a consensus bug, so these tests generate possibly
+//! invalid triples, including the small-order and non-canonical edge cases,
+//! and check that all paths agree, both with each other and with
+//! `reddsa::batch::Verifier` used directly.
+
+use std::{convert::TryFrom, marker::PhantomData};
+
+use proptest::prelude::*;
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+
+use redjubjub::{hazmat::*, *};
+
+/// The little-endian encoding of the JubJub scalar field order.
+const SCALAR_ORDER: [u8; 32] = [
+    0xb7, 0x2c, 0xf7, 0xd6, 0x5e, 0x0e, 0x97, 0xd0, 0x82, 0x10, 0xc8, 0xcc, 0x93, 0x20, 0x68, 0xa6,
+    0x00, 0x3b, 0x34, 0x01, 0x01, 0x3b, 0x67, 0x06, 0xa9, 0xaf, 0x33, 0x65, 0xea, 0xb4, 0x7d, 0x0e,
+];
+
+/// A non-canonical encoding of the identity, with the sign bit set on u = 0.
+const NON_CANONICAL_IDENTITY: [u8; 32] = {
+    let mut bytes = [0; 32];
+    bytes[0] = 1;
+    bytes[31] = 0x80;
+    bytes
+};
+
+/// How a valid signature is turned into a test case.
+#[derive(Copy, Clone, Debug)]
+enum Mutation {
+    /// Leave the signature valid.
+    None,
+    /// Flip one bit of the key, signature or message encoding.
+    FlipBit(usize),
+    /// Sign under the small-order key `Point::torsion(k)`, which the
+    /// cofactored equation accepts.
+    SmallOrderKey(u8),
+    /// Sign with a nonce commitment that has the torsion component
+    /// `Point::torsion(k)`.
+    TorsionNonce(u8),
+    /// Add the field order to `s`, giving a non-canonical scalar.
+    NonCanonicalS,
+    /// Replace `R` with a non-canonical point encoding.
+    NonCanonicalR,
+    /// Replace the key with a non-canonical point encoding.
+    NonCanonicalKey,
+}
+
+fn mutation_strategy() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        2 => Just(Mutation::None),
+        4 => any::<usize>().prop_map(Mutation::FlipBit),
+        1 => (0..8u8).prop_map(Mutation::SmallOrderKey),
+        1 => (1..8u8).prop_map(Mutation::TorsionNonce),
+        1 => Just(Mutation::NonCanonicalS),
+        1 => Just(Mutation::NonCanonicalR),
+        1 => Just(Mutation::NonCanonicalKey),
+    ]
+}
+
+/// A possibly-invalid signature, in encoded form.
+#[derive(Clone, Debug)]
+struct Case<T: SigType> {
+    vk: [u8; 32],
+    sig: [u8; 64],
+    msg: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SigType> batch::BatchItemSource for Case<T> {
+    type SigType = T;
+
+    fn vk_bytes(&self) -> VerificationKeyBytes<T> {
+        self.vk.into()
+    }
+
+    fn signature(&self) -> Signature<T> {
+        self.sig.into()
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.msg
+    }
+}
+
+/// Sign `msg` with the secret `sk` under the public key `vk`, using the
+/// nonce `r` but committing to `nonce_point` rather than `[r]B`.
+fn sign_raw(sk: Scalar, vk: Point, r: Scalar, nonce_point: Point, msg: &[u8]) -> [u8; 64] {
+    let r_bytes = nonce_point.to_bytes();
+    let c = HStar::default()
+        .update(r_bytes)
+        .update(vk.to_bytes())
+        .update(msg)
+        .finalize();
+    let s = r + c * sk;
+    let mut sig = [0; 64];
+    sig[..32].copy_from_slice(&r_bytes);
+    sig[32..].copy_from_slice(&s.to_bytes());
+    sig
+}
+
+/// The signature types, with the matching `reddsa` batch item constructor.
+trait ReddsaSigType: SigType {
+    fn reddsa_item(
+        vk: [u8; 32],
+        sig: [u8; 64],
+        msg: &[u8],
+    ) -> reddsa::batch::Item<reddsa::sapling::SpendAuth, reddsa::sapling::Binding>;
+}
+
+impl ReddsaSigType for SpendAuth {
+    fn reddsa_item(
+        vk: [u8; 32],
+        sig: [u8; 64],
+        msg: &[u8],
+    ) -> reddsa::batch::Item<reddsa::sapling::SpendAuth, reddsa::sapling::Binding> {
+        reddsa::batch::Item::from_spendauth(vk.into(), sig.into(), &msg)
+    }
+}
+
+impl ReddsaSigType for Binding {
+    fn reddsa_item(
+        vk: [u8; 32],
+        sig: [u8; 64],
+        msg: &[u8],
+    ) -> reddsa::batch::Item<reddsa::sapling::SpendAuth, reddsa::sapling::Binding> {
+        reddsa::batch::Item::from_binding(vk.into(), sig.into(), &msg)
+    }
+}
+
+fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar {
+    let mut bytes = [0; 64];
+    rng.fill_bytes(&mut bytes);
+    Scalar::from_bytes_wide(&bytes)
+}
+
+impl<T: ReddsaSigType> Case<T> {
+    fn new<R: RngCore + CryptoRng>(mut rng: R, msg: Vec<u8>, mutation: Mutation) -> Self {
+        let sk = SigningKey::<T>::new(&mut rng);
+        Self::signed_by(&sk, rng, msg, mutation)
+    }
+
+    /// A case for a signature by `sk`, which the mutation may replace.
+    fn signed_by<R: RngCore + CryptoRng>(
+        sk: &SigningKey<T>,
+        mut rng: R,
+        msg: Vec<u8>,
+        mutation: Mutation,
+    ) -> Self {
+        let vk = VerificationKey::from(sk).to_bytes();
+        let mut case = Case {
+            vk,
+            sig: sk.sign(&mut rng, &msg).into(),
+            msg,
+            _marker: PhantomData,
+        };
+
+        let basepoint = Point::basepoint::<T>();
+        match mutation {
+            Mutation::None => {}
+            Mutation::FlipBit(bit) => {
+                let bit = bit % (8 * (32 + 64 + case.msg.len()));
+                let (byte, mask) = (bit / 8, 1 << (bit % 8));
+                match byte {
+                    0..=31 => case.vk[byte] ^= mask,
+                    32..=95 => case.sig[byte - 32] ^= mask,
+                    _ => case.msg[byte - 96] ^= mask,
+                }
+            }
+            Mutation::SmallOrderKey(k) => {
+                let vk = Point::torsion(k);
+                // `[s]B - [c]vk - R` is `-[c]vk` for `s = r`, which the
+                // cofactor multiplication clears.
+                let r = random_scalar(&mut rng);
+                case.vk = vk.to_bytes();
+                case.sig = sign_raw(Scalar::zero(), vk, r, basepoint * r, &case.msg);
+            }
+            Mutation::TorsionNonce(k) => {
+                let sk_scalar = Scalar::from_bytes(&sk.to_bytes()).unwrap();
+                let vk = Point::from_bytes(&vk).unwrap();
+                let r = random_scalar(&mut rng);
+                let nonce_point = basepoint * r + Point::torsion(k);
+                case.sig = sign_raw(sk_scalar, vk, r, nonce_point, &case.msg);
+            }
+            Mutation::NonCanonicalS => {
+                let mut carry = 0u16;
+                for (s, l) in case.sig[32..].iter_mut().zip(SCALAR_ORDER.iter()) {
+                    let sum = *s as u16 + *l as u16 + carry;
+                    *s = sum as u8;
+                    carry = sum >> 8;
+                }
+                assert_eq!(carry, 0);
+            }
+            Mutation::NonCanonicalR => {
+                case.sig[..32].copy_from_slice(&NON_CANONICAL_IDENTITY);
+            }
+            Mutation::NonCanonicalKey => {
+                case.vk = NON_CANONICAL_IDENTITY;
+            }
+        }
+        case
+    }
+
+    /// Verify the case with [`VerificationKey::verify`].
+    fn verify_single(&self) -> bool {
+        VerificationKey::<T>::try_from(self.vk)
+            .and_then(|vk| vk.verify(&self.msg, &Signature::from(self.sig)))
+            .is_ok()
+    }
+
+    /// Verify the case with [`PreparedVerificationKey::verify`].
+    fn verify_prepared(&self) -> bool {
+        VerificationKey::<T>::try_from(self.vk)
+            .and_then(|vk| {
+                vk.precompute()
+                    .verify(&self.msg, &Signature::from(self.sig))
+            })
+            .is_ok()
+    }
+
+    /// Verify the case with [`batch::Item::verify_single`].
+    fn verify_item(&self) -> bool {
+        batch::Item::from(self).verify_single().is_ok()
+    }
+
+    /// Verify the case alone in a batch, and in a batch of valid signatures.
+    fn verify_batch<R: RngCore + CryptoRng>(&self, mut rng: R) -> (bool, bool) {
+        let mut alone = batch::Verifier::new();
+        alone.queue_from(self);
+        let alone = alone.verify(&mut rng).is_ok();
+
+        let mut mixed = batch::Verifier::new();
+        for i in 0..4u8 {
+            if i == 2 {
+                mixed.queue_from(self);
+            }
+            mixed.queue_from(&Case::<T>::new(&mut rng, vec![i], Mutation::None));
+        }
+        let mixed = mixed.verify(&mut rng).is_ok();
+
+        (alone, mixed)
+    }
+
+    /// Verify the case alone in a `reddsa::batch::Verifier`, and in one with
+    /// valid signatures.
+    fn verify_reddsa_batch<R: RngCore + CryptoRng>(&self, mut rng: R) -> (bool, bool) {
+        let mut alone = reddsa::batch::Verifier::new();
+        alone.queue(T::reddsa_item(self.vk, self.sig, &self.msg));
+        let alone = alone.verify(&mut rng).is_ok();
+
+        let mut mixed = reddsa::batch::Verifier::new();
+        mixed.queue(T::reddsa_item(self.vk, self.sig, &self.msg));
+        for i in 0..3u8 {
+            let other = Case::<T>::new(&mut rng, vec![i], Mutation::None);
+            mixed.queue(T::reddsa_item(other.vk, other.sig, &other.msg));
+        }
+        let mixed = mixed.verify(&mut rng).is_ok();
+
+        (alone, mixed)
+    }
+
+    /// Verify the case alone in a [`batch::KeyGroupedVerifier`], and in one
+    /// with valid signatures.
+    fn verify_key_grouped<R: RngCore + CryptoRng>(&self, mut rng: R) -> (bool, bool) {
+        let vk_bytes = VerificationKeyBytes::<T>::from(self.vk);
+
+        let mut alone = batch::KeyGroupedVerifier::new();
+        alone.queue((vk_bytes, self.sig.into(), &self.msg));
+        let alone = alone.verify(&mut rng).is_ok();
+
+        let mut mixed = batch::KeyGroupedVerifier::new();
+        mixed.queue((vk_bytes, self.sig.into(), &self.msg));
+        for i in 0..3u8 {
+            let other = Case::<T>::new(&mut rng, vec![i], Mutation::None);
+            mixed.queue((other.vk.into(), other.sig.into(), &other.msg));
+        }
+        let mixed = mixed.verify(&mut rng).is_ok();
+
+        (alone, mixed)
+    }
+
+    fn check<R: RngCore + CryptoRng>(&self, mut rng: R) {
+        let single = self.verify_single();
+        assert_eq!(single, self.verify_item(), "{:?}", self);
+        assert_eq!(single, self.verify_prepared(), "{:?}", self);
+        assert_eq!((single, single), self.verify_batch(&mut rng), "{:?}", self);
+        let reddsa = self.verify_reddsa_batch(&mut rng);
+        assert_eq!((single, single), reddsa, "{:?}", self);
+        assert_eq!(reddsa, self.verify_key_grouped(rng), "{:?}", self);
+    }
+}
+
+proptest! {
+    #[test]
+    fn single_and_batch_verification_agree(
+        mutation in mutation_strategy(),
+        msg in prop::collection::vec(any::<u8>(), 0..32),
+        rng_seed in prop::array::uniform32(any::<u8>()),
+    ) {
+        // Use a deterministic RNG so that test failures can be reproduced.
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+
+        let binding = Case::<Binding>::new(&mut rng, msg.clone(), mutation);
+        binding.check(&mut rng);
+
+        let spendauth = Case::<SpendAuth>::new(&mut rng, msg, mutation);
+        spendauth.check(&mut rng);
+    }
+}
+
+/// Check many possibly-invalid signatures against one prepared key, so that
+/// the tables are reused across signatures, and require the same result as
+/// [`VerificationKey::verify`] with the same key.
+fn check_prepared<T: ReddsaSigType, R: RngCore + CryptoRng>(mut rng: R, mutations: &[Mutation]) {
+    let sk = SigningKey::<T>::new(&mut rng);
+    let vk = VerificationKey::from(&sk);
+    let prepared = vk.precompute();
+    for (i, &mutation) in mutations.iter().enumerate() {
+        let case = Case::<T>::signed_by(&sk, &mut rng, vec![i as u8], mutation);
+        let sig = Signature::from(case.sig);
+        assert_eq!(
+            vk.verify(&case.msg, &sig),
+            prepared.verify(&case.msg, &sig),
+            "{:?}",
+            case
+        );
+    }
+}
+
+proptest! {
+    // Each case prepares two keys, which costs about eight verifications.
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prepared_and_unprepared_keys_agree(
+        mutations in prop::collection::vec(mutation_strategy(), 1..16),
+        rng_seed in prop::array::uniform32(any::<u8>()),
+    ) {
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+        check_prepared::<Binding, _>(&mut rng, &mutations);
+        check_prepared::<SpendAuth, _>(&mut rng, &mutations);
+    }
+}
+
+/// The edge cases should actually be exercised: the small-order and torsion
+/// cases verify, and the non-canonical cases do not.
+#[test]
+fn edge_cases_have_expected_validity() {
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    let msg = b"edge case".to_vec();
+    for (mutation, valid) in [
+        (Mutation::None, true),
+        (Mutation::SmallOrderKey(0), true),
+        (Mutation::SmallOrderKey(1), true),
+        (Mutation::SmallOrderKey(2), true),
+        (Mutation::SmallOrderKey(4), true),
+        (Mutation::TorsionNonce(1), true),
+        (Mutation::TorsionNonce(2), true),
+        (Mutation::TorsionNonce(4), true),
+        (Mutation::NonCanonicalS, false),
+        (Mutation::NonCanonicalR, false),
+        (Mutation::NonCanonicalKey, false),
+    ] {
+        let case = Case::<SpendAuth>::new(&mut rng, msg.clone(), mutation);
+        assert_eq!(case.verify_single(), valid, "{:?}", mutation);
+        case.check(&mut rng);
+    }
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "std")]
+
+use redjubjub::*;
+
+fn parse_key(bytes: [u8; 32]) -> Result<VerificationKey<SpendAuth>, Box<dyn std::error::Error>> {
+    Ok(VerificationKey::from_bytes(&bytes)?)
+}
+
+#[test]
+fn errors_box_and_display() {
+    let e = parse_key([0xff; 32]).unwrap_err();
+    assert_eq!(e.to_string(), "Malformed verification key encoding.");
+    assert_eq!(
+        e.downcast_ref::<Error>(),
+        Some(&Error::MalformedVerificationKey)
+    );
+}
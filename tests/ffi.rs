@@ -0,0 +1,172 @@
+#![cfg(feature = "ffi")]
+
+use std::ptr;
+
+use rand::thread_rng;
+use redjubjub::{ffi::*, *};
+
+#[test]
+fn sign_and_verify() {
+    let msg = b"ffi";
+    for sig_type in [REDJUBJUB_BINDING, REDJUBJUB_SPENDAUTH] {
+        let mut sk = [0u8; 32];
+        let mut vk = [0u8; 32];
+        let mut sig = [0u8; 64];
+        unsafe {
+            assert_eq!(
+                redjubjub_signing_key_generate(sig_type, sk.as_mut_ptr()),
+                REDJUBJUB_OK
+            );
+            assert_eq!(
+                redjubjub_verification_key(sig_type, sk.as_ptr(), vk.as_mut_ptr()),
+                REDJUBJUB_OK
+            );
+            assert_eq!(
+                redjubjub_sign(
+                    sig_type,
+                    sk.as_ptr(),
+                    msg.as_ptr(),
+                    msg.len(),
+                    sig.as_mut_ptr()
+                ),
+                REDJUBJUB_OK
+            );
+            assert_eq!(
+                redjubjub_verify(sig_type, vk.as_ptr(), msg.as_ptr(), msg.len(), sig.as_ptr()),
+                REDJUBJUB_OK
+            );
+            assert_eq!(
+                redjubjub_verify(sig_type, vk.as_ptr(), ptr::null(), 0, sig.as_ptr()),
+                REDJUBJUB_ERR_INVALID_SIGNATURE
+            );
+        }
+    }
+}
+
+#[test]
+fn invalid_arguments() {
+    let mut out = [0u8; 64];
+    unsafe {
+        assert_eq!(
+            redjubjub_signing_key_generate(2, out.as_mut_ptr()),
+            REDJUBJUB_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            redjubjub_signing_key_generate(REDJUBJUB_BINDING, ptr::null_mut()),
+            REDJUBJUB_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            redjubjub_sign(
+                REDJUBJUB_BINDING,
+                ptr::null(),
+                ptr::null(),
+                0,
+                out.as_mut_ptr()
+            ),
+            REDJUBJUB_ERR_INVALID_ARGUMENT
+        );
+        assert_eq!(
+            redjubjub_verification_key(REDJUBJUB_BINDING, [0xffu8; 32].as_ptr(), out.as_mut_ptr()),
+            REDJUBJUB_ERR_MALFORMED_SIGNING_KEY
+        );
+    }
+}
+
+#[test]
+fn batch_verify() {
+    let mut rng = thread_rng();
+    let msgs: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; i as usize]).collect();
+    let mut sig_types = Vec::new();
+    let mut vks = Vec::new();
+    let mut sigs = Vec::new();
+    for (i, msg) in msgs.iter().enumerate() {
+        if i % 2 == 0 {
+            let sk = SigningKey::<SpendAuth>::new(&mut rng);
+            sig_types.push(REDJUBJUB_SPENDAUTH);
+            vks.extend_from_slice(&<[u8; 32]>::from(VerificationKey::from(&sk)));
+            sigs.extend_from_slice(&<[u8; 64]>::from(sk.sign(&mut rng, msg)));
+        } else {
+            let sk = SigningKey::<Binding>::new(&mut rng);
+            sig_types.push(REDJUBJUB_BINDING);
+            vks.extend_from_slice(&<[u8; 32]>::from(VerificationKey::from(&sk)));
+            sigs.extend_from_slice(&<[u8; 64]>::from(sk.sign(&mut rng, msg)));
+        }
+    }
+    let msg_ptrs: Vec<*const u8> = msgs.iter().map(|m| m.as_ptr()).collect();
+    let msg_lens: Vec<usize> = msgs.iter().map(|m| m.len()).collect();
+
+    let batch_verify = |sigs: &[u8]| unsafe {
+        redjubjub_batch_verify(
+            msgs.len(),
+            sig_types.as_ptr(),
+            vks.as_ptr(),
+            msg_ptrs.as_ptr(),
+            msg_lens.as_ptr(),
+            sigs.as_ptr(),
+        )
+    };
+    assert_eq!(batch_verify(&sigs), REDJUBJUB_OK);
+
+    sigs[64] ^= 1;
+    assert_eq!(batch_verify(&sigs), REDJUBJUB_ERR_INVALID_SIGNATURE);
+
+    let mut invalid_index = usize::MAX;
+    let find_invalid = |sigs: &[u8], invalid_index: &mut usize| unsafe {
+        redjubjub_batch_verify_find_invalid(
+            msgs.len(),
+            sig_types.as_ptr(),
+            vks.as_ptr(),
+            msg_ptrs.as_ptr(),
+            msg_lens.as_ptr(),
+            sigs.as_ptr(),
+            invalid_index,
+        )
+    };
+    assert_eq!(
+        find_invalid(&sigs, &mut invalid_index),
+        REDJUBJUB_ERR_INVALID_SIGNATURE
+    );
+    assert_eq!(invalid_index, 1);
+
+    sigs[64] ^= 1;
+    invalid_index = usize::MAX;
+    assert_eq!(find_invalid(&sigs, &mut invalid_index), REDJUBJUB_OK);
+    assert_eq!(invalid_index, usize::MAX);
+}
+
+#[test]
+fn randomize() {
+    let rng = thread_rng();
+    let sk = SigningKey::<SpendAuth>::new(rng);
+    let randomizer = Randomizer::new(thread_rng());
+    let sk_bytes: [u8; 32] = sk.into();
+    let vk_bytes: [u8; 32] = VerificationKey::from(&sk).into();
+    let r_bytes: [u8; 32] = randomizer.into();
+
+    let mut rsk = [0u8; 32];
+    let mut rvk = [0u8; 32];
+    unsafe {
+        assert_eq!(
+            redjubjub_randomize_signing_key(sk_bytes.as_ptr(), r_bytes.as_ptr(), rsk.as_mut_ptr()),
+            REDJUBJUB_OK
+        );
+        assert_eq!(
+            redjubjub_randomize_verification_key(
+                vk_bytes.as_ptr(),
+                r_bytes.as_ptr(),
+                rvk.as_mut_ptr()
+            ),
+            REDJUBJUB_OK
+        );
+    }
+    assert_eq!(rsk, <[u8; 32]>::from(sk.randomize(&randomizer)));
+    assert_eq!(
+        rvk,
+        <[u8; 32]>::from(VerificationKey::from(&sk).randomize(&randomizer))
+    );
+}
+
+#[test]
+fn self_test() {
+    assert_eq!(redjubjub_self_test(), REDJUBJUB_OK);
+}
@@ -0,0 +1,73 @@
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn display_round_trips_through_from_str() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let vk_bytes = VerificationKeyBytes::from(vk);
+    let sig = sk.sign(thread_rng(), b"from_str");
+
+    assert_eq!(vk.to_string().parse(), Ok(vk));
+    assert_eq!(vk_bytes.to_string().parse(), Ok(vk_bytes));
+    assert_eq!(sig.to_string().parse(), Ok(sig));
+
+    // Upper-case hex is accepted too.
+    assert_eq!(vk.to_string().to_uppercase().parse(), Ok(vk));
+    assert_eq!(sig.to_string().to_uppercase().parse(), Ok(sig));
+}
+
+#[test]
+fn malformed_strings_are_rejected() {
+    let sk = SigningKey::<Binding>::new(thread_rng());
+    let vk = VerificationKey::from(&sk).to_string();
+    let sig = sk.sign(thread_rng(), b"from_str").to_string();
+
+    for s in [
+        String::new(),
+        vk[..63].to_string(),
+        format!("{}0", vk),
+        format!(" {}", &vk[1..]),
+        format!("0x{}", &vk[2..]),
+        format!("g{}", &vk[1..]),
+    ] {
+        assert_eq!(
+            s.parse::<VerificationKeyBytes<Binding>>(),
+            Err(Error::MalformedVerificationKey),
+            "{:?}",
+            s
+        );
+        assert_eq!(
+            s.parse::<VerificationKey<Binding>>(),
+            Err(Error::MalformedVerificationKey),
+            "{:?}",
+            s
+        );
+    }
+    for s in [
+        String::new(),
+        sig[..127].to_string(),
+        format!("{}00", sig),
+        format!("{} ", &sig[..127]),
+    ] {
+        assert_eq!(
+            s.parse::<Signature<Binding>>(),
+            Err(Error::MalformedSignature),
+            "{:?}",
+            s
+        );
+    }
+}
+
+#[test]
+fn invalid_key_encodings_are_rejected() {
+    // Well-formed hex, but not the encoding of a curve point.
+    let hex = "ff".repeat(32);
+    let bytes: VerificationKeyBytes<SpendAuth> = hex.parse().unwrap();
+    assert!(VerificationKey::try_from(bytes).is_err());
+    assert_eq!(
+        hex.parse::<VerificationKey<SpendAuth>>(),
+        Err(Error::MalformedVerificationKey)
+    );
+}
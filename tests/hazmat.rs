@@ -0,0 +1,124 @@
+use rand::thread_rng;
+
+use redjubjub::{hazmat::*, *};
+
+#[test]
+fn hash_to_scalar_matches_hstar() {
+    let data = b"some data";
+    assert_eq!(
+        hash_to_scalar(H_STAR_PERSONALIZATION, data),
+        HStar::default()
+            .update(&data[..4])
+            .update(&data[4..])
+            .finalize()
+    );
+    assert_ne!(
+        hash_to_scalar(b"Some_OtherDomain", data),
+        hash_to_scalar(H_STAR_PERSONALIZATION, data)
+    );
+}
+
+fn check_basepoint<T: SigType>() {
+    let sk = SigningKey::<T>::new(thread_rng());
+    let sk_scalar = Scalar::from_bytes(&sk.to_bytes()).unwrap();
+    assert_eq!(
+        Point::basepoint::<T>() * sk_scalar,
+        Point::from(VerificationKey::from(&sk))
+    );
+}
+
+#[test]
+fn basepoints_match_key_derivation() {
+    check_basepoint::<Binding>();
+    check_basepoint::<SpendAuth>();
+}
+
+/// Check the verification equation `[S]B = R + [c]vk` using an externally
+/// computed challenge.
+#[test]
+fn challenge_matches_signing() {
+    let msg = b"challenge";
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let sig = sk.sign(thread_rng(), msg).to_bytes();
+    let r_bytes: [u8; 32] = sig[..32].try_into().unwrap();
+    let s_bytes: [u8; 32] = sig[32..].try_into().unwrap();
+
+    let c = HStar::default()
+        .update(r_bytes)
+        .update(vk.to_bytes())
+        .update(msg)
+        .finalize();
+
+    assert_eq!(
+        Point::basepoint::<SpendAuth>() * Scalar::from_bytes(&s_bytes).unwrap(),
+        Point::from_bytes(&r_bytes).unwrap() + Point::from(vk) * c
+    );
+}
+
+#[test]
+fn arithmetic() {
+    let a = Scalar::from_bytes_wide(&[1; 64]);
+    let b = Scalar::from_bytes_wide(&[2; 64]);
+    assert_eq!(a + b - b, a);
+    assert_eq!(a + -a, Scalar::zero());
+    assert_eq!(a * a.invert().unwrap(), Scalar::one());
+    assert_eq!(Scalar::zero().invert(), None);
+    assert_eq!(Scalar::from_bytes(&a.to_bytes()), Some(a));
+    assert_eq!(Scalar::from_bytes(&[0xff; 32]), None);
+
+    let g = Point::basepoint::<Binding>();
+    assert_eq!(g * a + g * b, g * (a + b));
+    assert_eq!(g - g, Point::identity());
+    assert_eq!(g + -g, Point::identity());
+    assert_eq!(Point::from_bytes(&g.to_bytes()), Some(g));
+
+    // A randomized key is the original key plus the randomizer times the basepoint.
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let randomizer = Randomizer::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    assert_eq!(
+        Point::from(vk.randomize(&randomizer)),
+        Point::from(vk) + Point::basepoint::<SpendAuth>() * Scalar::from(randomizer)
+    );
+}
+
+#[test]
+fn torsion_points_have_expected_orders() {
+    assert_eq!(Point::torsion(0), Point::identity());
+    assert_eq!(Point::torsion(8), Point::identity());
+    for k in 1..8u8 {
+        let t = Point::torsion(k);
+        assert!(t.is_small_order());
+        assert!(!t.is_torsion_free());
+        let order = [8, 4, 8, 2, 8, 4, 8][usize::from(k - 1)];
+        let mut multiple = Point::identity();
+        for i in 1..=order {
+            multiple = multiple + t;
+            assert_eq!(multiple == Point::identity(), i == order, "k = {}", k);
+        }
+    }
+    assert!(Point::basepoint::<SpendAuth>().is_torsion_free());
+    assert!(!Point::basepoint::<SpendAuth>().is_small_order());
+}
+
+#[test]
+fn constructed_signatures_verify_as_expected() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let msg = b"consensus";
+    let nonce = hash_to_scalar(b"Test_Nonce______", msg);
+    let commitment = Point::basepoint::<SpendAuth>() * nonce;
+
+    let sig = sign_with_commitment(&sk, nonce, commitment, msg);
+    assert!(vk.verify(msg, &sig).is_ok());
+
+    // The cofactored equation accepts a torsion component in R.
+    let torsioned = sign_with_commitment(&sk, nonce, commitment + Point::torsion(1), msg);
+    assert!(vk.verify(msg, &torsioned).is_ok());
+
+    let unreduced = with_unreduced_s(&sig);
+    assert_eq!(unreduced.r_bytes(), sig.r_bytes());
+    assert_ne!(unreduced.s_bytes(), sig.s_bytes());
+    assert!(vk.verify(msg, &unreduced).is_err());
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "serde")]
+
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn signature_json_is_hex() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let sig = sk.sign(thread_rng(), b"json");
+    let sig_bytes: [u8; 64] = sig.into();
+
+    let json = serde_json::to_string(&sig).unwrap();
+    assert_eq!(json.len(), 2 * 64 + 2);
+    assert_eq!(json, format!("\"{}\"", sig));
+    assert!(json[1..].starts_with(&format!("{:02x}", sig_bytes[0])));
+
+    let decoded: Signature<SpendAuth> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, sig);
+}
+
+#[test]
+fn keys_json_round_trip() {
+    let sk = SigningKey::<Binding>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let vk_bytes = VerificationKeyBytes::from(vk);
+
+    let vk_json = serde_json::to_string(&vk).unwrap();
+    let vk_bytes_json = serde_json::to_string(&vk_bytes).unwrap();
+    assert_eq!(vk_json, vk_bytes_json);
+    assert_eq!(vk_json, format!("\"{}\"", vk_bytes));
+
+    let decoded_vk: VerificationKey<Binding> = serde_json::from_str(&vk_json).unwrap();
+    assert_eq!(decoded_vk, vk);
+
+    let sk_json = serde_json::to_string(&sk).unwrap();
+    assert_eq!(sk_json.len(), 2 * 32 + 2);
+    let decoded_sk: SigningKey<Binding> = serde_json::from_str(&sk_json).unwrap();
+    assert_eq!(VerificationKey::from(&decoded_sk), vk);
+}
+
+#[test]
+fn malformed_json_hex_is_rejected() {
+    // Wrong length, non-hex digits, and non-string values must all fail.
+    assert!(serde_json::from_str::<Signature<SpendAuth>>("\"00\"").is_err());
+    assert!(
+        serde_json::from_str::<VerificationKeyBytes<SpendAuth>>(&format!(
+            "\"{}\"",
+            "zz".repeat(32)
+        ))
+        .is_err()
+    );
+    assert!(serde_json::from_str::<VerificationKeyBytes<SpendAuth>>("[0, 1]").is_err());
+}
+
+#[test]
+fn hex_helpers_round_trip() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let sig_bytes: [u8; 64] = sk.sign(thread_rng(), b"hex").into();
+    let vk_bytes: [u8; 32] = VerificationKey::from(&sk).into();
+
+    let sig_hex = encoding::encode_hex_64(&sig_bytes);
+    assert_eq!(encoding::decode_hex_64(sig_hex), Some(sig_bytes));
+    assert_eq!(
+        std::str::from_utf8(&sig_hex).unwrap(),
+        Signature::<SpendAuth>::from(sig_bytes).to_string()
+    );
+
+    let vk_hex = encoding::encode_hex_32(&vk_bytes);
+    assert_eq!(encoding::decode_hex_32(vk_hex), Some(vk_bytes));
+    assert_eq!(
+        encoding::decode_hex_32(vk_hex.to_ascii_uppercase()),
+        Some(vk_bytes)
+    );
+
+    assert_eq!(encoding::decode_hex_32(&vk_hex[1..]), None);
+    assert_eq!(encoding::decode_hex_32([b'g'; 64]), None);
+}
+
+#[test]
+fn randomizer_json_round_trip() {
+    let randomizer = Randomizer::new(thread_rng());
+    let json = serde_json::to_string(&randomizer).unwrap();
+    let hex = encoding::encode_hex_32(&randomizer.to_bytes());
+    assert_eq!(json, format!("\"{}\"", std::str::from_utf8(&hex).unwrap()));
+    assert_eq!(
+        serde_json::from_str::<Randomizer>(&json).unwrap(),
+        randomizer
+    );
+
+    // Non-canonical encodings are rejected.
+    let json = format!("\"{}\"", "ff".repeat(32));
+    assert!(serde_json::from_str::<Randomizer>(&json).is_err());
+}
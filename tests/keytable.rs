@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+use rand::thread_rng;
+
+use redjubjub::{keytable::KeyTable, *};
+
+fn keys(n: usize) -> Vec<SigningKey<SpendAuth>> {
+    (0..n).map(|_| SigningKey::new(thread_rng())).collect()
+}
+
+#[test]
+fn round_trip_and_verify() {
+    let sks = keys(8);
+    let bytes = KeyTable::encode(
+        sks.iter()
+            .map(|sk| VerificationKeyBytes::from(VerificationKey::from(sk))),
+    );
+    assert_eq!(bytes.len(), 16 + 32 * 8);
+
+    let table = KeyTable::<SpendAuth>::from_bytes(&bytes).unwrap();
+    assert_eq!(table.len(), 8);
+    assert_eq!(table.validate_all(), Ok(()));
+    assert!(table.get(8).is_none());
+
+    let msgs: Vec<[u8; 4]> = (0..8u32).map(|i| i.to_le_bytes()).collect();
+    let sigs: Vec<_> = sks
+        .iter()
+        .zip(&msgs)
+        .map(|(sk, msg)| sk.sign(thread_rng(), msg))
+        .collect();
+    for (i, (msg, sig)) in msgs.iter().zip(&sigs).enumerate() {
+        assert_eq!(
+            table.get(i).unwrap().unwrap().to_bytes(),
+            VerificationKey::from(&sks[i]).to_bytes()
+        );
+        assert!(table.verify(i, msg, sig).is_ok());
+        assert!(table.verify((i + 1) % 8, msg, sig).is_err());
+    }
+
+    let batch = || {
+        msgs.iter()
+            .zip(&sigs)
+            .enumerate()
+            .map(|(i, (msg, sig))| (i, &msg[..], *sig))
+    };
+    assert!(table.batch_verify(batch(), thread_rng()).is_ok());
+    assert!(table
+        .batch_verify(
+            batch().map(|(i, msg, sig)| ((i + 1) % 8, msg, sig)),
+            thread_rng()
+        )
+        .is_err());
+    assert_eq!(
+        table.batch_verify(batch().map(|(_, msg, sig)| (8, msg, sig)), thread_rng()),
+        Err(Error::MalformedVerificationKey)
+    );
+}
+
+#[test]
+fn invalid_keys_are_found_lazily() {
+    let good = VerificationKeyBytes::from(VerificationKey::from(&keys(1)[0]));
+    let bad = VerificationKeyBytes::<SpendAuth>::from([0xff; 32]);
+    let bytes = KeyTable::encode([good, bad, good]);
+
+    let table = KeyTable::<SpendAuth>::from_bytes(&bytes).unwrap();
+    assert_eq!(table.get_bytes(1), Some(bad));
+    assert_eq!(table.validate_all(), Err(1));
+    assert_eq!(
+        table.get(1).unwrap().err(),
+        Some(Error::MalformedVerificationKey)
+    );
+    assert!(table.get(2).unwrap().is_ok());
+
+    let sig = Signature::from([0; 64]);
+    assert_eq!(
+        table.batch_verify([(1, &b""[..], sig)], thread_rng()),
+        Err(Error::MalformedVerificationKey)
+    );
+
+    // Batch verification decodes keys that were never looked up.
+    let fresh = KeyTable::<SpendAuth>::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        fresh.batch_verify([(1, &b""[..], sig)], thread_rng()),
+        Err(Error::MalformedVerificationKey)
+    );
+}
+
+#[test]
+fn malformed_headers_are_rejected() {
+    let bytes = KeyTable::<SpendAuth>::encode([VerificationKeyBytes::from([0; 32])]);
+    assert!(KeyTable::<SpendAuth>::from_bytes(&bytes).is_ok());
+
+    // Wrong signature type.
+    assert_eq!(
+        KeyTable::<Binding>::from_bytes(&bytes).err(),
+        Some(Error::MalformedKeyTable)
+    );
+    // Truncated, extended, or with a corrupted magic, version, or reserved byte.
+    assert!(KeyTable::<SpendAuth>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(KeyTable::<SpendAuth>::from_bytes(&[&bytes[..], &[0]].concat()).is_err());
+    for offset in [0, 4, 6, 8] {
+        let mut corrupted = bytes.clone();
+        corrupted[offset] ^= 1;
+        assert!(KeyTable::<SpendAuth>::from_bytes(&corrupted).is_err());
+    }
+    assert!(KeyTable::<SpendAuth>::from_bytes(&[]).is_err());
+}
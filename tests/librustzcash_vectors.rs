@@ -23,6 +23,60 @@ fn verify_librustzcash_binding() {
     }
 }
 
+/// Every vector must survive a byte-level round trip through this crate's
+/// types unchanged, so keys and signatures can be exchanged with
+/// `librustzcash` as raw bytes.
+#[test]
+fn librustzcash_encodings_round_trip() {
+    for (_, sig, pk_bytes) in LIBRUSTZCASH_SPENDAUTH_SIGS.iter() {
+        let sig_bytes = sig.to_bytes();
+        assert_eq!(
+            Signature::<SpendAuth>::from_bytes(&sig_bytes).to_bytes(),
+            sig_bytes
+        );
+        let vk = VerificationKey::try_from(*pk_bytes).unwrap();
+        assert_eq!(vk.to_bytes(), pk_bytes.to_bytes());
+    }
+    for (_, sig, pk_bytes) in LIBRUSTZCASH_BINDING_SIGS.iter() {
+        let sig_bytes = sig.to_bytes();
+        assert_eq!(
+            Signature::<Binding>::from_bytes(&sig_bytes).to_bytes(),
+            sig_bytes
+        );
+        let vk = VerificationKey::try_from(*pk_bytes).unwrap();
+        assert_eq!(vk.to_bytes(), pk_bytes.to_bytes());
+    }
+}
+
+/// The non-canonical encoding of the point `(0, 1)`: `u = 0` with the sign
+/// bit set.
+const NON_CANONICAL: [u8; 32] = {
+    let mut bytes = [0; 32];
+    bytes[0] = 1;
+    bytes[31] = 0x80;
+    bytes
+};
+
+/// Unlike `librustzcash` with ZIP 216 disabled (before Canopy), this crate
+/// always rejects non-canonical point encodings, in both verification keys
+/// and the `R` component of signatures.
+#[test]
+fn zip216_non_canonical_points_are_rejected() {
+    assert_eq!(
+        VerificationKey::<SpendAuth>::try_from(NON_CANONICAL).err(),
+        Some(Error::MalformedVerificationKey)
+    );
+
+    let (msg, sig, pk_bytes) = &LIBRUSTZCASH_SPENDAUTH_SIGS[0];
+    let mut sig_bytes = sig.to_bytes();
+    sig_bytes[..32].copy_from_slice(&NON_CANONICAL);
+    let vk = VerificationKey::try_from(*pk_bytes).unwrap();
+    assert_eq!(
+        vk.verify(msg, &Signature::from(sig_bytes)),
+        Err(Error::InvalidSignature)
+    );
+}
+
 lazy_static! {
     static ref LIBRUSTZCASH_SPENDAUTH_SIGS: [(
         Vec<u8>,
@@ -0,0 +1,38 @@
+use rand::thread_rng;
+
+use redjubjub::{hazmat::Point, params::*, *};
+
+/// The values from §5.4.7 of the Zcash protocol specification.
+#[test]
+fn params_match_specification() {
+    assert_eq!(H_STAR_PERSONALIZATION, b"Zcash_RedJubjubH");
+    assert_eq!(SPENDAUTHSIG_BASEPOINT_PERSONALIZATION, b"Zcash_G_");
+    assert_eq!(BINDINGSIG_BASEPOINT_PERSONALIZATION, b"Zcash_cv");
+    assert_eq!(KEY_LENGTH, 32);
+    assert_eq!(SIGNATURE_LENGTH, 64);
+}
+
+fn check_basepoint<T: SigType>(bytes: [u8; 32]) {
+    let point = jubjub::AffinePoint::from_bytes(bytes).unwrap();
+    assert!(bool::from(point.is_prime_order()));
+    assert_eq!(Point::basepoint::<T>().to_bytes(), bytes);
+
+    // The basepoint is the verification key of the signing key 1.
+    let mut one = [0; KEY_LENGTH];
+    one[0] = 1;
+    let sk = SigningKey::<T>::from_bytes(&one).unwrap();
+    assert_eq!(VerificationKey::from(&sk).to_bytes(), bytes);
+
+    let sk = SigningKey::<T>::new(thread_rng());
+    assert_eq!(sk.to_bytes().len(), KEY_LENGTH);
+    assert_eq!(
+        sk.sign(thread_rng(), b"").to_bytes().len(),
+        SIGNATURE_LENGTH
+    );
+}
+
+#[test]
+fn basepoints_are_prime_order_generators() {
+    check_basepoint::<SpendAuth>(SPENDAUTHSIG_BASEPOINT_BYTES);
+    check_basepoint::<Binding>(BINDINGSIG_BASEPOINT_BYTES);
+}
@@ -8,6 +8,7 @@ use redjubjub::*;
 /// A signature test-case, containing signature data and expected validity.
 #[derive(Clone, Debug)]
 struct SignatureCase<T: SigType> {
+    sk: SigningKey<T>,
     msg: Vec<u8>,
     sig: Signature<T>,
     pk_bytes: VerificationKeyBytes<T>,
@@ -24,16 +25,10 @@ enum Tweak {
     ChangeMessage,
     /// Change the public key the signature is defined for, invalidating the signature.
     ChangePubkey,
-    /* XXX implement this -- needs to regenerate a custom signature because the
-       nonce commitment is fed into the hash, so it has to have torsion at signing
-       time.
     /// Change the case to have a torsion component in the signature's `r` value.
     AddTorsion,
-    */
-    /* XXX implement this -- needs custom handling of field arithmetic.
     /// Change the signature's `s` scalar to be unreduced (mod L), invalidating the signature.
     UnreducedScalar,
-    */
 }
 
 impl<T: SigType> SignatureCase<T> {
@@ -43,6 +38,7 @@ impl<T: SigType> SignatureCase<T> {
         let pk_bytes = VerificationKey::from(&sk).into();
         let invalid_pk_bytes = VerificationKey::from(&SigningKey::new(&mut rng)).into();
         Self {
+            sk,
             msg,
             sig,
             pk_bytes,
@@ -85,6 +81,24 @@ impl<T: SigType> SignatureCase<T> {
                 self.pk_bytes = self.invalid_pk_bytes;
                 self.is_valid = false;
             }
+            Tweak::AddTorsion => {
+                // Re-sign the (possibly changed) message with a torsion
+                // component in the nonce commitment, which the cofactored
+                // verification equation accepts. This replaces any earlier
+                // change to the signature, so the case is valid again unless
+                // the public key was changed.
+                let nonce = hazmat::hash_to_scalar(b"Test_Nonce______", &self.sig.to_bytes());
+                let commitment =
+                    hazmat::Point::basepoint::<T>() * nonce + hazmat::Point::torsion(1);
+                self.sig = hazmat::sign_with_commitment(&self.sk, nonce, commitment, &self.msg);
+                self.is_valid =
+                    self.pk_bytes == VerificationKeyBytes::from(VerificationKey::from(&self.sk));
+            }
+            Tweak::UnreducedScalar => {
+                // A non-canonical `s` is always rejected.
+                self.sig = hazmat::with_unreduced_s(&self.sig);
+                self.is_valid = false;
+            }
         }
     }
 }
@@ -94,6 +108,8 @@ fn tweak_strategy() -> impl Strategy<Value = Tweak> {
         10 => Just(Tweak::None),
         1 => Just(Tweak::ChangeMessage),
         1 => Just(Tweak::ChangePubkey),
+        1 => Just(Tweak::AddTorsion),
+        1 => Just(Tweak::UnreducedScalar),
     ]
 }
 
@@ -132,12 +148,7 @@ proptest! {
         // Use a deterministic RNG so that test failures can be reproduced.
         let mut rng = ChaChaRng::from_seed(rng_seed);
 
-        let r = {
-            // XXX-jubjub: better API for this
-            let mut bytes = [0; 64];
-            rng.fill_bytes(&mut bytes[..]);
-            Randomizer::from_bytes_wide(&bytes)
-        };
+        let r = Randomizer::new(&mut rng);
 
         let sk = SigningKey::<SpendAuth>::new(&mut rng);
         let pk = VerificationKey::from(&sk);
@@ -151,3 +162,113 @@ proptest! {
         assert_eq!(pk_r_via_pk_rand, pk_r_via_sk_rand);
     }
 }
+
+proptest! {
+    #[test]
+    fn deterministic_signing_is_reproducible(
+        msg in prop::collection::vec(any::<u8>(), 0..64),
+        rng_seed in prop::array::uniform32(any::<u8>()),
+    ) {
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+
+        let sk = SigningKey::<SpendAuth>::new(&mut rng);
+        let vk = VerificationKey::from(&sk);
+
+        let sig = sk.sign_deterministic(&msg);
+        assert_eq!(sig, sk.sign_deterministic(&msg));
+        assert!(vk.verify(&msg, &sig).is_ok());
+
+        // A different message must give an unrelated signature.
+        let mut other_msg = msg.clone();
+        other_msg.push(0);
+        let other_sig = sk.sign_deterministic(&other_msg);
+        let sig_bytes: [u8; 64] = sig.into();
+        let other_sig_bytes: [u8; 64] = other_sig.into();
+        assert_ne!(&sig_bytes[..32], &other_sig_bytes[..32]);
+    }
+}
+
+proptest! {
+    #[test]
+    fn seed_derivation_is_reproducible(
+        seed in prop::collection::vec(any::<u8>(), 32..64),
+        info in prop::collection::vec(any::<u8>(), 0..16),
+    ) {
+        let sk = SigningKey::<SpendAuth>::from_seed(&seed, &info);
+        assert_eq!(sk.to_bytes(), SigningKey::<SpendAuth>::from_seed(&seed, &info).to_bytes());
+
+        // Moving a byte from the seed into the info must change the key.
+        let (short_seed, last) = seed.split_at(seed.len() - 1);
+        let longer_info = [last, &info[..]].concat();
+        let other = SigningKey::<SpendAuth>::from_seed(short_seed, &longer_info);
+        assert_ne!(sk.to_bytes(), other.to_bytes());
+
+        let mut other_info = info.clone();
+        other_info.push(0);
+        let other = SigningKey::<SpendAuth>::from_seed(&seed, &other_info);
+        assert_ne!(sk.to_bytes(), other.to_bytes());
+    }
+}
+
+#[test]
+fn seed_derivation_matches_spec() {
+    let seed = [7u8; 32];
+    let info = b"m/32'/133'/0'";
+    let mut input = 32u64.to_le_bytes().to_vec();
+    input.extend_from_slice(&seed);
+    input.extend_from_slice(info);
+    let hash = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(b"RedJubjubKeyDerv")
+        .hash(&input);
+    let expected = jubjub::Scalar::from_bytes_wide(hash.as_array()).to_bytes();
+
+    assert_eq!(
+        SigningKey::<Binding>::from_seed(&seed, info).to_bytes(),
+        expected
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn randomize_many_matches_randomize(
+        n in 0usize..24,
+        rng_seed in prop::array::uniform32(any::<u8>()),
+    ) {
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+        let vk = VerificationKey::from(&SigningKey::<SpendAuth>::new(&mut rng));
+        let randomizers: Vec<_> = (0..n).map(|_| Randomizer::new(&mut rng)).collect();
+
+        let expected: Vec<[u8; 32]> = randomizers.iter().map(|r| vk.randomize(r).into()).collect();
+        let actual: Vec<[u8; 32]> = vk.randomize_many(&randomizers).into_iter().map(Into::into).collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+proptest! {
+    #[test]
+    fn signature_parts_round_trip(
+        msg in prop::collection::vec(any::<u8>(), 0..64),
+        rng_seed in prop::array::uniform32(any::<u8>()),
+    ) {
+        let mut rng = ChaChaRng::from_seed(rng_seed);
+        let sk = SigningKey::<Binding>::new(&mut rng);
+        let sig = sk.sign(&mut rng, &msg);
+
+        let (r_bytes, s_bytes) = (sig.r_bytes(), sig.s_bytes());
+        assert_eq!([r_bytes, s_bytes].concat(), sig.to_bytes().to_vec());
+        assert_eq!(Signature::<Binding>::from_parts(r_bytes, s_bytes), Ok(sig));
+
+        // A non-canonical scalar, or a byte string that is not a point.
+        assert_eq!(
+            Signature::<Binding>::from_parts(r_bytes, [0xff; 32]),
+            Err(Error::MalformedSignature)
+        );
+        assert_eq!(
+            Signature::<Binding>::from_parts([0xff; 32], s_bytes),
+            Err(Error::MalformedSignature)
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn randomized_signing_key_keeps_linkage() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let ak = VerificationKey::from(&sk);
+    let randomizer = Randomizer::new(thread_rng());
+
+    let rsk = RandomizedSigningKey::new(&sk, randomizer);
+    assert_eq!(rsk.randomizer(), randomizer);
+    assert_eq!(rsk.original_verification_key(), ak);
+    assert_eq!(rsk.verification_key(), ak.randomize(&randomizer));
+    assert_eq!(
+        rsk.signing_key().to_bytes(),
+        sk.randomize(&randomizer).to_bytes()
+    );
+
+    let msg = b"spend";
+    let sig = rsk.sign(thread_rng(), msg);
+    assert!(rsk.verification_key().verify(msg, &sig).is_ok());
+
+    let rk = rsk.verification_key();
+    assert!(rk.is_randomization_of(&rsk.original_verification_key(), &rsk.randomizer()));
+    assert!(!rk.is_randomization_of(&ak, &Randomizer::new(thread_rng())));
+    assert!(!rk.is_randomization_of(&rk, &randomizer));
+}
+
+#[test]
+fn randomized_signing_key_debug_hides_secrets() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let rsk = RandomizedSigningKey::new(&sk, Randomizer::new(thread_rng()));
+    let debug = format!("{:?}", rsk);
+    assert!(debug.contains(&rsk.verification_key().to_string()));
+    let sk_hex = encoding::encode_hex_32(&rsk.signing_key().to_bytes());
+    assert!(!debug.contains(std::str::from_utf8(&sk_hex).unwrap()));
+}
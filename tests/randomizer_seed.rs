@@ -0,0 +1,35 @@
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn derivation_is_deterministic() {
+    let seed = RandomizerSeed::new(thread_rng());
+    let copy = RandomizerSeed::from(seed.to_bytes());
+    for index in [0, 1, 2, u64::MAX] {
+        assert_eq!(seed.derive(index), copy.derive(index));
+    }
+    assert_ne!(seed.derive(0), seed.derive(1));
+    assert_ne!(seed.derive(0), RandomizerSeed::new(thread_rng()).derive(0));
+}
+
+#[test]
+fn derived_randomizers_randomize_consistently() {
+    let seed = RandomizerSeed::from([7; 32]);
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let ak = VerificationKey::from(&sk);
+    let msg = b"multi-spend transaction";
+
+    for index in 0..4 {
+        let randomizer = seed.derive(index);
+        let rsk = sk.randomize(&randomizer);
+        let rk = ak.randomize(&randomizer);
+        assert!(rk.verify(msg, &rsk.sign(thread_rng(), msg)).is_ok());
+    }
+}
+
+#[test]
+fn debug_is_redacted() {
+    let seed = RandomizerSeed::from([0x42; 32]);
+    assert_eq!(format!("{:?}", seed), "RandomizerSeed(\"<redacted>\")");
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use rand::thread_rng;
+
+use redjubjub::{reverify::ReverifyGuard, *};
+
+#[test]
+fn guard_remembers_valid_signatures() {
+    let mut rng = thread_rng();
+    let mut guard = ReverifyGuard::new();
+
+    let sk = SigningKey::<SpendAuth>::new(&mut rng);
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    let msg = b"mempool tx";
+    let sig = sk.sign(&mut rng, msg);
+
+    assert_eq!(guard.verified_generation(vk_bytes, &sig, msg), None);
+    assert!(guard.verify(vk_bytes, &sig, msg).is_ok());
+    assert_eq!(guard.verified_generation(vk_bytes, &sig, msg), Some(0));
+
+    // Confirming the signature in a later template rebuild bumps its generation.
+    assert_eq!(guard.advance_generation(), 1);
+    assert!(guard.verify(vk_bytes, &sig, msg).is_ok());
+    assert_eq!(guard.verified_generation(vk_bytes, &sig, msg), Some(1));
+    assert_eq!(guard.len(), 1);
+
+    assert!(guard.invalidate(vk_bytes, &sig, msg));
+    assert!(!guard.invalidate(vk_bytes, &sig, msg));
+    assert!(guard.is_empty());
+}
+
+#[test]
+fn guard_rejects_and_forgets_invalid_signatures() {
+    let mut rng = thread_rng();
+    let mut guard = ReverifyGuard::new();
+
+    let sk = SigningKey::<Binding>::new(&mut rng);
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    let sig = sk.sign(&mut rng, b"good");
+
+    assert!(guard.verify(vk_bytes, &sig, b"bad").is_err());
+    assert_eq!(guard.verified_generation(vk_bytes, &sig, b"bad"), None);
+    assert!(guard.is_empty());
+}
+
+#[test]
+fn guard_prunes_stale_generations() {
+    let mut rng = thread_rng();
+    let mut guard = ReverifyGuard::new();
+
+    let mut triples = Vec::new();
+    for i in 0..4u8 {
+        let sk = SigningKey::<SpendAuth>::new(&mut rng);
+        let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+        let msg = [i];
+        let sig = sk.sign(&mut rng, &msg);
+        assert!(guard.verify(vk_bytes, &sig, &msg).is_ok());
+        triples.push((vk_bytes, sig, msg));
+        guard.advance_generation();
+    }
+
+    assert_eq!(guard.invalidate_before(2), 2);
+    for (i, (vk_bytes, sig, msg)) in triples.iter().enumerate() {
+        let expected = if i < 2 { None } else { Some(i as u64) };
+        assert_eq!(guard.verified_generation(*vk_bytes, sig, msg), expected);
+    }
+
+    guard.clear();
+    assert!(guard.is_empty());
+    assert_eq!(guard.generation(), 4);
+}
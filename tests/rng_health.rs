@@ -0,0 +1,72 @@
+use rand::thread_rng;
+use rand_chacha::ChaChaRng;
+use rand_core::{impls, CryptoRng, RngCore, SeedableRng};
+
+use redjubjub::*;
+
+/// An RNG whose output is a fixed pattern of period `period`.
+struct BrokenRng {
+    period: u8,
+    next: u8,
+}
+
+impl RngCore for BrokenRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest {
+            *b = self.next;
+            self.next = (self.next + 1) % self.period;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for BrokenRng {}
+
+#[test]
+fn broken_rngs_are_refused() {
+    // Constant output, a short cycle, and a period that repeats every 32 bytes.
+    for period in [1, 7, 32] {
+        let rng = || BrokenRng { period, next: 0 };
+        assert_eq!(
+            SigningKey::<SpendAuth>::new_checked(rng()).err(),
+            Some(Error::WeakRandomness)
+        );
+        let sk = SigningKey::<Binding>::new(thread_rng());
+        assert_eq!(
+            sk.sign_checked(rng(), b"msg").err(),
+            Some(Error::WeakRandomness)
+        );
+    }
+}
+
+#[test]
+fn healthy_rngs_match_unchecked() {
+    let sk = SigningKey::<SpendAuth>::new_checked(ChaChaRng::seed_from_u64(1)).unwrap();
+    assert_eq!(
+        sk.to_bytes(),
+        SigningKey::<SpendAuth>::new(ChaChaRng::seed_from_u64(1)).to_bytes()
+    );
+
+    let sig = sk
+        .sign_checked(ChaChaRng::seed_from_u64(2), b"msg")
+        .unwrap();
+    assert_eq!(sig, sk.sign(ChaChaRng::seed_from_u64(2), b"msg"));
+    assert!(VerificationKey::from(&sk).verify(b"msg", &sig).is_ok());
+
+    for _ in 0..100 {
+        let sk = SigningKey::<Binding>::new_checked(thread_rng()).unwrap();
+        assert!(sk.sign_checked(thread_rng(), b"msg").is_ok());
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "secrecy")]
+
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn secret_signing_key_signs_and_redacts() {
+    let sk = SigningKey::<SpendAuth>::new_secret(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let msg = b"secrecy";
+
+    let sig = sk.expose_secret().sign(thread_rng(), msg);
+    assert!(vk.verify(msg, &sig).is_ok());
+
+    let debug = format!("{:?}", sk);
+    assert!(debug.contains("redacted"));
+    let sk_hex = encoding::encode_hex_32(&sk.expose_secret().to_bytes());
+    assert!(!debug.contains(std::str::from_utf8(&sk_hex).unwrap()));
+
+    let copy = sk.clone();
+    assert_eq!(
+        copy.expose_secret().to_bytes(),
+        sk.expose_secret().to_bytes()
+    );
+}
+
+#[test]
+fn secret_signing_key_holds_the_same_key() {
+    let sk = SigningKey::<Binding>::new(thread_rng());
+    let secret = SecretSigningKey::from(sk);
+    assert_eq!(secret.expose_secret().to_bytes(), sk.to_bytes());
+    assert_eq!(
+        VerificationKeyBytes::from(VerificationKey::from(&secret)),
+        VerificationKeyBytes::from(VerificationKey::from(&sk))
+    );
+}
@@ -0,0 +1,13 @@
+#[test]
+fn self_test_passes() {
+    assert_eq!(redjubjub::self_test(), Ok(()));
+}
+
+#[test]
+fn build_info_describes_this_build() {
+    let info = redjubjub::build_info();
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert!(info.algorithms.contains(&"RedJubjub-SpendAuthSig"));
+    assert_eq!(info.key_table_version, 1);
+    assert_eq!(info, redjubjub::build_info());
+}
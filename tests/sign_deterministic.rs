@@ -0,0 +1,74 @@
+use redjubjub::{hazmat::*, *};
+
+/// Block `i` of the documented nonce input, `BLAKE2b-512(sk || msg || LE64(i))`.
+fn block(sk: &[u8; 32], msg: &[u8], i: u64) -> [u8; 64] {
+    *blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(b"RedJubjubDetSign")
+        .to_state()
+        .update(sk)
+        .update(msg)
+        .update(&i.to_le_bytes())
+        .finalize()
+        .as_array()
+}
+
+/// Recompute a deterministic signature from the derivation documented on
+/// `SigningKey::sign_deterministic`, independently of the RNG plumbing.
+fn expected_signature<T: SigType>(sk: &SigningKey<T>, msg: &[u8]) -> [u8; 64] {
+    let sk_bytes = sk.to_bytes();
+    let vk_bytes = VerificationKey::from(sk).to_bytes();
+
+    // `reddsa` draws exactly 80 bytes from the RNG for the nonce.
+    let mut random = [0; 80];
+    random[..64].copy_from_slice(&block(&sk_bytes, msg, 0));
+    random[64..].copy_from_slice(&block(&sk_bytes, msg, 1)[..16]);
+    let nonce = HStar::default()
+        .update(random)
+        .update(vk_bytes)
+        .update(msg)
+        .finalize();
+
+    let r_bytes = (Point::basepoint::<T>() * nonce).to_bytes();
+    let c = HStar::default()
+        .update(r_bytes)
+        .update(vk_bytes)
+        .update(msg)
+        .finalize();
+    let s = nonce + c * Scalar::from_bytes(&sk_bytes).unwrap();
+
+    let mut sig = [0; 64];
+    sig[..32].copy_from_slice(&r_bytes);
+    sig[32..].copy_from_slice(&s.to_bytes());
+    sig
+}
+
+#[test]
+fn sign_deterministic_matches_documented_derivation() {
+    for msg in [&b""[..], b"message", &[0xab; 200]] {
+        let spendauth = SigningKey::<SpendAuth>::from_seed(&[1; 32], b"kat");
+        let sig: [u8; 64] = spendauth.sign_deterministic(msg).into();
+        assert_eq!(sig, expected_signature(&spendauth, msg));
+
+        let binding = SigningKey::<Binding>::from_seed(&[2; 32], b"kat");
+        let sig: [u8; 64] = binding.sign_deterministic(msg).into();
+        assert_eq!(sig, expected_signature(&binding, msg));
+    }
+}
+
+/// Pins the output, so that any change to the derivation, or to how many bytes
+/// `reddsa` draws for the nonce, is caught.
+#[test]
+fn sign_deterministic_known_answer() {
+    let mut sk_bytes = [0x42; 32];
+    sk_bytes[31] = 0x04;
+    let sk = SigningKey::<SpendAuth>::try_from(sk_bytes).unwrap();
+    let sig: [u8; 64] = sk.sign_deterministic(b"redjubjub KAT").into();
+    assert_eq!(
+        std::str::from_utf8(&encoding::encode_hex_64(&sig)).unwrap(),
+        concat!(
+            "98f60a3d7da0fd233223debd5184f821a5a959e485c02f88f5facaa2967b690f",
+            "fdeb1925f38d74b0c1b4f421cbe97ba5541f8735096ee5ce971abfb74b0c790d",
+        )
+    );
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "signature-traits")]
+
+use rand::thread_rng;
+use signature::{Keypair, RandomizedSigner, Signer, Verifier};
+
+use redjubjub::*;
+
+#[test]
+fn signer_and_verifier_traits() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = sk.verifying_key();
+    let msg = b"signature traits";
+
+    let sig: Signature<SpendAuth> = Signer::sign(&sk, msg);
+    assert_eq!(sig, sk.sign_deterministic(msg));
+    assert!(Verifier::verify(&vk, msg, &sig).is_ok());
+
+    // The trait methods are not shadowed by inherent methods of the same name.
+    let sig: Signature<SpendAuth> = sk.try_sign(msg).unwrap();
+    assert!(Verifier::verify(&vk, msg, &sig).is_ok());
+
+    let sig: Signature<SpendAuth> = sk.sign_with_rng(&mut thread_rng(), msg);
+    assert!(Verifier::verify(&vk, msg, &sig).is_ok());
+    assert!(Verifier::verify(&vk, b"other message", &sig).is_err());
+}
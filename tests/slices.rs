@@ -0,0 +1,58 @@
+use std::convert::TryFrom;
+
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn slices_parse_like_arrays() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk = VerificationKey::from(&sk);
+    let sig = sk.sign(thread_rng(), b"slices");
+    let randomizer = Randomizer::new(thread_rng());
+
+    let sk_bytes = sk.to_bytes();
+    let vk_bytes = vk.to_bytes();
+    let sig_bytes = sig.to_bytes();
+    let randomizer_bytes = randomizer.to_bytes();
+
+    let parsed = SigningKey::<SpendAuth>::try_from(&sk_bytes[..]).unwrap();
+    assert_eq!(parsed.to_bytes(), sk_bytes);
+    assert_eq!(VerificationKey::try_from(&vk_bytes[..]), Ok(vk));
+    assert_eq!(
+        VerificationKeyBytes::try_from(&vk_bytes[..]),
+        Ok(VerificationKeyBytes::from(vk))
+    );
+    assert_eq!(Signature::try_from(&sig_bytes[..]), Ok(sig));
+    let parsed = Randomizer::try_from(&randomizer_bytes[..]).unwrap();
+    assert_eq!(parsed.to_bytes(), randomizer_bytes);
+}
+
+#[test]
+fn wrong_lengths_are_rejected() {
+    let bytes = [0u8; 65];
+    for len in [0, 31, 33, 64] {
+        assert_eq!(
+            SigningKey::<Binding>::try_from(&bytes[..len]).err(),
+            Some(Error::MalformedSigningKey)
+        );
+        assert_eq!(
+            VerificationKey::<Binding>::try_from(&bytes[..len]).err(),
+            Some(Error::MalformedVerificationKey)
+        );
+        assert_eq!(
+            VerificationKeyBytes::<Binding>::try_from(&bytes[..len]).err(),
+            Some(Error::MalformedVerificationKey)
+        );
+        assert_eq!(
+            Randomizer::try_from(&bytes[..len]).err(),
+            Some(Error::MalformedRandomizer)
+        );
+    }
+    for len in [0, 32, 63, 65] {
+        assert_eq!(
+            Signature::<Binding>::try_from(&bytes[..len]).err(),
+            Some(Error::MalformedSignature)
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use std::convert::TryFrom;
+
+use rand::thread_rng;
+
+use redjubjub::*;
+
+/// The encoding of the identity point (u, v) = (0, 1).
+const IDENTITY: [u8; 32] = {
+    let mut bytes = [0; 32];
+    bytes[0] = 1;
+    bytes
+};
+
+/// The encoding of (1, 0), a point of order 4 on any Edwards curve.
+const ORDER_4: [u8; 32] = {
+    let mut bytes = [0; 32];
+    bytes[31] = 0x80;
+    bytes
+};
+
+#[test]
+fn identity_is_classified() {
+    let vk_bytes = VerificationKeyBytes::<SpendAuth>::from(IDENTITY);
+    assert_eq!(vk_bytes.is_identity(), Ok(true));
+    assert_eq!(vk_bytes.is_small_order(), Ok(true));
+    // Small-order keys are still accepted by parsing, per the RedDSA spec.
+    assert!(VerificationKey::try_from(vk_bytes).is_ok());
+}
+
+#[test]
+fn order_4_point_is_classified() {
+    let vk_bytes = VerificationKeyBytes::<Binding>::from(ORDER_4);
+    assert_eq!(vk_bytes.is_identity(), Ok(false));
+    assert_eq!(vk_bytes.is_small_order(), Ok(true));
+}
+
+#[test]
+fn random_keys_are_not_small_order() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk_bytes = VerificationKeyBytes::from(VerificationKey::from(&sk));
+    assert_eq!(vk_bytes.is_identity(), Ok(false));
+    assert_eq!(vk_bytes.is_small_order(), Ok(false));
+}
+
+#[test]
+fn malformed_keys_are_rejected() {
+    // The v-coordinate 2^255 - 1 is larger than the field modulus.
+    let vk_bytes = VerificationKeyBytes::<SpendAuth>::from([0xff; 32]);
+    assert_eq!(
+        vk_bytes.is_small_order(),
+        Err(Error::MalformedVerificationKey)
+    );
+    assert_eq!(vk_bytes.is_identity(), Err(Error::MalformedVerificationKey));
+}
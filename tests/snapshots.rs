@@ -0,0 +1,116 @@
+//! Snapshot tests of serialized encodings.
+//!
+//! Every encoding this crate produces is checked against the committed
+//! fixtures in `tests/fixtures/snapshots.txt`, so that a change to the bytes
+//! of any public type (on any platform) fails the build. All inputs are
+//! derived deterministically.
+//!
+//! When an encoding is changed on purpose, regenerate the fixtures with
+//!
+//! ```shell
+//! REDJUBJUB_UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+//! ```
+//!
+//! and commit the result along with the change.
+
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use std::{collections::BTreeMap, fmt::Write, path::PathBuf};
+
+use redjubjub::{keytable::KeyTable, *};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        write!(out, "{:02x}", b).unwrap();
+        out
+    })
+}
+
+/// Add the encodings of the keys and signatures of type `T` to `snapshots`.
+fn collect<T: SigType>(name: &str, snapshots: &mut BTreeMap<String, String>) {
+    let sk = SigningKey::<T>::from_seed(&[0x42; 32], name.as_bytes());
+    let vk = VerificationKey::from(&sk);
+    let vk_bytes = VerificationKeyBytes::from(vk);
+    let sig = sk.sign_deterministic(b"snapshot message");
+
+    let mut add = |field: &str, value: String| {
+        snapshots.insert(format!("{}.{}", name, field), value);
+    };
+    add("signing_key", hex(&sk.to_bytes()));
+    add("verification_key", hex(&vk.to_bytes()));
+    add("signature", hex(&<[u8; 64]>::from(sig)));
+    add("signature.display", sig.to_string());
+    add(
+        "signing_key.bincode",
+        hex(&bincode::serialize(&sk).unwrap()),
+    );
+    add(
+        "verification_key.bincode",
+        hex(&bincode::serialize(&vk).unwrap()),
+    );
+    add(
+        "verification_key_bytes.bincode",
+        hex(&bincode::serialize(&vk_bytes).unwrap()),
+    );
+    add("signature.bincode", hex(&bincode::serialize(&sig).unwrap()));
+    add("signature.json", serde_json::to_string(&sig).unwrap());
+    add("verification_key.json", serde_json::to_string(&vk).unwrap());
+    add(
+        "key_table",
+        hex(&KeyTable::<T>::encode([vk_bytes, vk_bytes])),
+    );
+}
+
+fn snapshots() -> BTreeMap<String, String> {
+    let mut snapshots = BTreeMap::new();
+    collect::<Binding>("binding", &mut snapshots);
+    collect::<SpendAuth>("spendauth", &mut snapshots);
+
+    let randomizer = Randomizer::from_bytes_wide(&[0x17; 64]);
+    let ak = VerificationKey::from(&SigningKey::<SpendAuth>::from_seed(&[0x42; 32], b"ak"));
+    snapshots.insert("randomizer".into(), hex(&randomizer.to_bytes()));
+    snapshots.insert(
+        "randomizer.bincode".into(),
+        hex(&bincode::serialize(&randomizer).unwrap()),
+    );
+    snapshots.insert(
+        "randomizer.json".into(),
+        serde_json::to_string(&randomizer).unwrap(),
+    );
+    snapshots.insert(
+        "randomizer_seed.derive_7".into(),
+        hex(&RandomizerSeed::from([0x42; 32]).derive(7).to_bytes()),
+    );
+    snapshots.insert(
+        "randomized_verification_key".into(),
+        hex(&ak.randomize(&randomizer).to_bytes()),
+    );
+    snapshots.insert(
+        "build_info.known_answer_digest".into(),
+        hex(&build_info().known_answer_digest),
+    );
+    snapshots
+}
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/snapshots.txt")
+}
+
+#[test]
+fn encodings_match_snapshots() {
+    let actual = snapshots()
+        .into_iter()
+        .map(|(name, value)| format!("{} = {}\n", name, value))
+        .collect::<String>();
+
+    let path = fixture_path();
+    if std::env::var_os("REDJUBJUB_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap();
+    for (expected, actual) in expected.lines().zip(actual.lines()) {
+        assert_eq!(expected, actual);
+    }
+    assert_eq!(expected.lines().count(), actual.lines().count());
+}
@@ -0,0 +1,64 @@
+use rand::thread_rng;
+
+use redjubjub::*;
+
+#[test]
+fn raw_verification_matches_typed_verification() {
+    let sk = SigningKey::<SpendAuth>::new(thread_rng());
+    let vk_bytes = VerificationKey::from(&sk).to_bytes();
+    let msg = b"raw bytes";
+    let sig_bytes = sk.sign(thread_rng(), msg).to_bytes();
+
+    assert!(verify::<SpendAuth>(vk_bytes, msg, sig_bytes).is_ok());
+    assert_eq!(
+        verify::<SpendAuth>(vk_bytes, b"other message", sig_bytes),
+        Err(Error::InvalidSignature)
+    );
+    // A signature is only valid for its own signature type.
+    assert_eq!(
+        verify::<Binding>(vk_bytes, msg, sig_bytes),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        verify::<SpendAuth>([0xff; 32], msg, sig_bytes),
+        Err(Error::MalformedVerificationKey)
+    );
+    let mut unreduced = sig_bytes;
+    unreduced[63] = 0xff;
+    assert_eq!(
+        verify::<SpendAuth>(vk_bytes, msg, unreduced),
+        Err(Error::InvalidSignature)
+    );
+}
+
+#[test]
+fn raw_batch_items_verify() {
+    let mut rng = thread_rng();
+    let mut batch = batch::Verifier::new();
+    for i in 0..8u32 {
+        let msg = i.to_le_bytes();
+        let item = if i % 2 == 0 {
+            let sk = SigningKey::<SpendAuth>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg);
+            batch::Item::from_raw::<SpendAuth, _>(
+                VerificationKey::from(&sk).to_bytes(),
+                &msg,
+                sig.to_bytes(),
+            )
+        } else {
+            let sk = SigningKey::<Binding>::new(&mut rng);
+            let sig = sk.sign(&mut rng, &msg);
+            batch::Item::from_raw::<Binding, _>(
+                VerificationKey::from(&sk).to_bytes(),
+                &msg,
+                sig.to_bytes(),
+            )
+        };
+        assert!(item.clone().verify_single().is_ok());
+        batch.queue(item);
+    }
+    assert!(batch.verify(&mut rng).is_ok());
+
+    let item = batch::Item::from_raw::<Binding, _>([0xff; 32], b"msg", [0; 64]);
+    assert_eq!(item.verify_single(), Err(Error::MalformedVerificationKey));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "wasm")]
+
+// These exercise the bindings natively; error paths construct JavaScript
+// values and can only run under a JavaScript host.
+
+use redjubjub::wasm::{
+    JsBatchVerifier as BatchVerifier, JsSigType as SigType, JsSignature as Signature,
+    JsSigningKey as SigningKey, JsVerificationKey as VerificationKey,
+};
+
+#[test]
+fn sign_and_verify() {
+    for sig_type in [SigType::Binding, SigType::SpendAuth] {
+        let sk = SigningKey::generate(sig_type);
+        let sk = SigningKey::from_bytes(sig_type, &sk.to_bytes()).unwrap();
+        assert_eq!(sk.sig_type(), sig_type);
+
+        let vk = sk.verification_key();
+        let vk = VerificationKey::from_bytes(sig_type, &vk.to_bytes()).unwrap();
+        assert_eq!(vk.sig_type(), sig_type);
+
+        let sig = sk.sign(b"wasm");
+        let sig = Signature::from_bytes(&sig.to_bytes()).unwrap();
+        assert!(vk.verify(b"wasm", &sig));
+        assert!(!vk.verify(b"other", &sig));
+    }
+}
+
+#[test]
+fn batch_verify() {
+    let binding = SigningKey::generate(SigType::Binding);
+    let spendauth = SigningKey::generate(SigType::SpendAuth);
+
+    let mut verifier = BatchVerifier::new();
+    verifier.queue(&binding.verification_key(), b"a", &binding.sign(b"a"));
+    verifier.queue(&spendauth.verification_key(), b"b", &spendauth.sign(b"b"));
+    assert!(verifier.verify());
+
+    let mut verifier = BatchVerifier::new();
+    verifier.queue(&binding.verification_key(), b"a", &spendauth.sign(b"a"));
+    assert!(!verifier.verify());
+}